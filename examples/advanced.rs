@@ -49,7 +49,7 @@ pub fn main() {
             ctx.then("it is still empty", |env| assert!(env.set.is_empty()));
         });
 
-        ctx.then("panic!(…) fails", |_env| {
+        ctx.then::<_, _, ()>("panic!(…) fails", |_env| {
             panic!("Some reason for failure.")
         });
     }));
@@ -8,29 +8,99 @@
 //! Running these tests and doing asserts is not the job of the Context, but the Runner.
 //!
 
-use block::{Block, Example};
+use std::any::Any;
+use std::borrow::Cow;
+use std::fs;
+#[cfg(feature = "futures")]
+use std::future::Future;
+use std::panic::Location;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use block::lets::SUBJECT;
+use block::{Block, Example, Lets, MappedContext, SharedEnv, SharedExamples};
 use header::{ContextHeader, ContextLabel, ExampleHeader, ExampleLabel};
-use report::ExampleResult;
+use report::{ExampleResult, IntoExampleResult};
+
+pub(crate) type ContextHook<'a, T> = Arc<dyn Fn(&mut T) + Send + Sync + 'a>;
+
+pub(crate) type AroundHook<'a, T> = Arc<dyn Fn(&mut T, RunExample<T>) + Send + Sync + 'a>;
+
+/// The reason for skipping an example, see
+/// [`before_each_or_skip`](struct.Context.html#method.before_each_or_skip).
+pub type SkipReason = String;
+
+pub(crate) type BeforeEachHook<'a, T> =
+    Arc<dyn Fn(&mut T) -> Option<SkipReason> + Send + Sync + 'a>;
+
+pub(crate) type SkipPredicate<'a> = Arc<dyn Fn() -> bool + Send + Sync + 'a>;
+
+/// Handle passed to [`around`](struct.Context.html#method.around) blocks
+/// for running the block they wrap.
+pub struct RunExample<'a, T: 'a> {
+    run: &'a mut dyn FnMut(&mut T),
+}
+
+impl<'a, T> RunExample<'a, T> {
+    pub(crate) fn new(run: &'a mut dyn FnMut(&mut T)) -> Self {
+        RunExample { run }
+    }
+
+    /// Runs the wrapped block (including its `before_each`/`after_each` blocks)
+    /// with the given environment.
+    pub fn call(self, environment: &mut T) {
+        (self.run)(environment)
+    }
+}
 
 /// Test contexts are a convenient tool for adding structure and code sharing to a test suite.
-pub struct Context<T> {
+///
+/// As blocks may get evaluated in parallel, their closures must be both `Send` and `Sync`:
+///
+/// ```compile_fail
+/// # extern crate rspec;
+/// #
+/// # use std::rc::Rc;
+/// #
+/// # pub fn main() {
+/// let not_send = Rc::new(42);
+/// rspec::suite("a test suite", (), move |ctx| {
+///     ctx.example("an example", move |_env| *not_send == 42);
+/// });
+/// # }
+/// ```
+pub struct Context<'a, T> {
     pub(crate) header: Option<ContextHeader>,
-    pub(crate) blocks: Vec<Block<T>>,
-    pub(crate) before_all: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) before_each: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) after_all: Vec<Box<dyn Fn(&mut T)>>,
-    pub(crate) after_each: Vec<Box<dyn Fn(&mut T)>>,
+    /// Names of the enclosing (named) contexts, including the context's own name.
+    pub(crate) path: Vec<Cow<'static, str>>,
+    pub(crate) blocks: Vec<Block<'a, T>>,
+    pub(crate) before_all: Vec<ContextHook<'a, T>>,
+    pub(crate) before_each: Vec<BeforeEachHook<'a, T>>,
+    pub(crate) after_all: Vec<ContextHook<'a, T>>,
+    pub(crate) after_each: Vec<ContextHook<'a, T>>,
+    pub(crate) around: Vec<AroundHook<'a, T>>,
+    /// Predicate for skipping the context at run time, and the reason for doing so.
+    pub(crate) skip_if: Option<(SkipPredicate<'a>, String)>,
+    /// Groups of examples registered within the context (or any of its ancestors).
+    pub(crate) shared_examples: SharedExamples<'a, T>,
+    /// Whether the context's blocks get evaluated serially, regardless of the configuration.
+    pub(crate) serial: bool,
 }
 
-impl<T> Context<T> {
+impl<'a, T> Context<'a, T> {
     pub(crate) fn new(header: Option<ContextHeader>) -> Self {
         Context {
+            path: header.iter().map(|header| header.name.clone()).collect(),
             header,
             blocks: vec![],
             before_all: vec![],
             before_each: vec![],
             after_all: vec![],
             after_each: vec![],
+            around: vec![],
+            skip_if: None,
+            shared_examples: SharedExamples::new(),
+            serial: false,
         }
     }
 
@@ -42,18 +112,68 @@ impl<T> Context<T> {
         self.blocks.iter().map(|b| b.num_examples()).sum()
     }
 
+    /// The number of contexts (including name-less scopes) nested within the context.
+    pub fn num_contexts(&self) -> usize {
+        self.blocks.iter().map(|b| b.num_contexts()).sum()
+    }
+
+    /// The maximum number of levels of contexts nested within the context.
+    pub fn max_depth(&self) -> usize {
+        self.blocks.iter().map(|b| b.max_depth()).max().unwrap_or(0)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.blocks.is_empty()
     }
-}
 
-// Both `Send` and `Sync` are necessary for parallel threaded execution.
-unsafe impl<T> Send for Context<T> where T: Send {}
+    /// A copy of the context, containing only the examples whose path passes `predicate`
+    /// (and the contexts containing any of them).
+    pub(crate) fn filtered(&self, predicate: &dyn Fn(&[&str]) -> bool) -> Context<'a, T> {
+        Context {
+            header: self.header.clone(),
+            path: self.path.clone(),
+            blocks: self
+                .blocks
+                .iter()
+                .filter_map(|block| block.filtered(predicate))
+                .collect(),
+            before_all: self.before_all.clone(),
+            before_each: self.before_each.clone(),
+            after_all: self.after_all.clone(),
+            after_each: self.after_each.clone(),
+            around: self.around.clone(),
+            skip_if: self.skip_if.clone(),
+            shared_examples: self.shared_examples.clone(),
+            serial: self.serial,
+        }
+    }
+
+    /// Prepends `prefix` to the paths of the context and all of its blocks,
+    /// e.g. for nesting it within another context after it has been built.
+    pub(crate) fn prefix_paths(&mut self, prefix: &[Cow<'static, str>]) {
+        self.path = prefix.iter().chain(&self.path).cloned().collect();
+        for block in self.blocks.iter_mut() {
+            match block {
+                Block::Context(ref mut context) => context.prefix_paths(prefix),
+                Block::Mapped(ref mut context) => context.prefix_paths(prefix),
+                Block::Example(ref mut example) => {
+                    example.path = prefix.iter().chain(&example.path).cloned().collect();
+                }
+            }
+        }
+    }
 
-// Both `Send` and `Sync` are necessary for parallel threaded execution.
-unsafe impl<T> Sync for Context<T> where T: Sync {}
+    /// Whether the context contains an example with the given path.
+    pub(crate) fn contains_example(&self, path: &[&str]) -> bool {
+        self.blocks.iter().any(|block| match block {
+            Block::Context(ref context) => context.contains_example(path),
+            Block::Mapped(ref context) => context.contains_example(path),
+            Block::Example(ref example) => example.path == path,
+        })
+    }
+}
 
-impl<T> Context<T>
+impl<'a, T> Context<'a, T>
 where
     T: Clone,
 {
@@ -94,16 +214,15 @@ where
     /// Available aliases:
     ///
     /// - [`specify`](struct.Context.html#method.specify).
+    /// - [`given`](struct.Context.html#method.given).
     /// - [`when`](struct.Context.html#method.when).
-    pub fn context<F>(&mut self, name: &'static str, body: F)
+    pub fn context<N, F>(&mut self, name: N, body: F)
     where
-        F: FnOnce(&mut Context<T>),
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::Context,
-            name,
-        };
+        let header = ContextHeader::new(ContextLabel::Context, name);
         self.context_internal(Some(header), body)
     }
 
@@ -111,16 +230,15 @@ where
     ///
     /// Available further aliases:
     ///
+    /// - [`given`](struct.Context.html#method.given).
     /// - [`when`](struct.Context.html#method.when).
-    pub fn specify<F>(&mut self, name: &'static str, body: F)
+    pub fn specify<N, F>(&mut self, name: N, body: F)
     where
-        F: FnOnce(&mut Context<T>),
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::Specify,
-            name,
-        };
+        let header = ContextHeader::new(ContextLabel::Specify, name);
         self.context_internal(Some(header), body)
     }
 
@@ -129,15 +247,72 @@ where
     /// Available further aliases:
     ///
     /// - [`specify`](struct.Context.html#method.specify).
-    pub fn when<F>(&mut self, name: &'static str, body: F)
+    /// - [`when`](struct.Context.html#method.when).
+    pub fn given<N, F>(&mut self, name: N, body: F)
     where
-        F: FnOnce(&mut Context<T>),
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
         T: ::std::fmt::Debug,
     {
-        let header = ContextHeader {
-            label: ContextLabel::When,
-            name,
-        };
+        let header = ContextHeader::new(ContextLabel::Given, name);
+        self.context_internal(Some(header), body)
+    }
+
+    /// Alias for [`context`](struct.Context.html#method.context), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`specify`](struct.Context.html#method.specify).
+    /// - [`given`](struct.Context.html#method.given).
+    pub fn when<N, F>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::When, name);
+        self.context_internal(Some(header), body)
+    }
+
+    /// Open and name a new context within the current context,
+    /// rendered with the given domain-specific label (rather than "Context").
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.context_as("Scenario", "a user logs in", |_ctx| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    ///
+    /// Corresponding console output:
+    ///
+    /// ```text
+    /// tests:
+    /// Suite "a test suite":
+    ///     Scenario "a user logs in":
+    ///         …
+    /// ```
+    pub fn context_as<N, F>(&mut self, label: &'static str, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
+        T: ::std::fmt::Debug,
+    {
+        let header = ContextHeader::new(ContextLabel::Context, name).with_display_label(label);
         self.context_internal(Some(header), body)
     }
 
@@ -186,22 +361,269 @@ where
     /// but not before `'It "tests c"'`.
     pub fn scope<F>(&mut self, body: F)
     where
-        F: FnOnce(&mut Context<T>),
+        F: FnOnce(&mut Context<'a, T>),
         T: ::std::fmt::Debug,
     {
         self.context_internal(None, body)
     }
 
+    /// Open a new name-less context within the current context, whose examples get reported
+    /// as ignored for the given reason if `predicate` returns `true` at run time.
+    ///
+    /// The predicate gets evaluated once, right before the context would get evaluated.
+    /// Neither the context's examples nor any of the hooks surrounding them get run if skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.skip_if(|| cfg!(windows), "not supported on windows", |ctx| {
+    ///         ctx.example("an example", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn skip_if<P, R, F>(&mut self, predicate: P, reason: R, body: F)
+    where
+        P: 'a + Fn() -> bool + Send + Sync,
+        R: Into<String>,
+        F: FnOnce(&mut Context<'a, T>),
+        T: ::std::fmt::Debug,
+    {
+        self.context_internal(None, |ctx| {
+            ctx.skip_if = Some((Arc::new(predicate), reason.into()));
+            body(ctx);
+        })
+    }
+
+    /// Open and name a new context within the current context, if the condition holds.
+    ///
+    /// Unlike with [`skip_if`](struct.Context.html#method.skip_if) the context doesn't get
+    /// reported as ignored if not, but isn't part of the suite at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # pub fn main() {
+    /// let suite = rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.context_if(cfg!(feature = "unstable"), "an unstable feature", |ctx| {
+    ///         ctx.example("an example", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// });
+    /// assert_eq!(0, suite.num_examples());
+    /// # }
+    /// ```
+    pub fn context_if<N, F>(&mut self, condition: bool, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
+        T: ::std::fmt::Debug,
+    {
+        if condition {
+            self.context(name, body);
+        }
+    }
+
+    /// Open and name a new context within the current context, whose blocks (and those of its
+    /// sub-contexts) get evaluated serially, even if the runner evaluates all others in parallel.
+    ///
+    /// This is useful for examples sharing a resource that doesn't allow for concurrent access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.serial_context("a shared resource", |ctx| {
+    ///         ctx.example("writes to it", |_env| {
+    ///             // …
+    ///         });
+    ///         ctx.example("writes to it too", |_env| {
+    ///             // …
+    ///         });
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn serial_context<N, F>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: FnOnce(&mut Context<'a, T>),
+        T: ::std::fmt::Debug,
+    {
+        self.context(name, |ctx| {
+            ctx.serial();
+            body(ctx);
+        })
+    }
+
+    /// Has the current context's blocks evaluated serially, regardless of the configuration,
+    /// as for [`serial_context`](struct.Context.html#method.serial_context).
+    ///
+    /// Sub-contexts opened after calling this inherit it.
+    pub fn serial(&mut self) -> &mut Self {
+        self.serial = true;
+        self
+    }
+
+    /// Register a named group of examples (and hooks) within the current context,
+    /// for the context and its subsequently opened sub-contexts to include by name
+    /// via [`behaves_like`](struct.Context.html#method.behaves_like).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", vec![1, 2, 3], |ctx| {
+    ///     ctx.shared_examples("a collection", |ctx| {
+    ///         ctx.it("is not empty", |env| !env.is_empty());
+    ///     });
+    ///     ctx.context("a full vec", |ctx| {
+    ///         ctx.behaves_like("a collection");
+    ///     });
+    ///     ctx.context("a truncated vec", |ctx| {
+    ///         ctx.before_each(|env| env.truncate(1));
+    ///         ctx.behaves_like("a collection");
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn shared_examples<N, F>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&mut Context<'a, T>) + Send + Sync,
+    {
+        self.shared_examples.register(name, body)
+    }
+
+    /// Register all groups of the given registry within the current context,
+    /// see [`shared_examples`](struct.Context.html#method.shared_examples).
+    pub fn use_shared_examples(&mut self, shared_examples: &SharedExamples<'a, T>) {
+        self.shared_examples.extend(shared_examples)
+    }
+
+    /// Include the group of examples registered under the given name
+    /// within a new name-less context, so that the group's hooks only apply to its own examples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no group has been registered under the given name.
+    #[track_caller]
+    pub fn behaves_like(&mut self, name: &str)
+    where
+        T: ::std::fmt::Debug,
+    {
+        let group = match self.shared_examples.get(name) {
+            Some(group) => group,
+            None => panic!("no shared examples registered as {:?}", name),
+        };
+        self.context_internal(None, |ctx| group(ctx))
+    }
+
+    /// Alias for [`behaves_like`](struct.Context.html#method.behaves_like), see for more info.
+    #[track_caller]
+    pub fn it_behaves_like(&mut self, name: &str)
+    where
+        T: ::std::fmt::Debug,
+    {
+        self.behaves_like(name)
+    }
+
+    /// Open and name a new context within the current context, whose environment
+    /// gets derived from the current context's environment by `map`.
+    ///
+    /// This allows for narrowing the environment down to what the context's examples
+    /// actually need (e.g. from an application's state to its database connection).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (42, "answer"), |ctx| {
+    ///     ctx.context_map("the number", |env| env.0, |ctx| {
+    ///         ctx.it("is 42", |number| *number == 42);
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn context_map<N, U, M, F>(&mut self, name: N, map: M, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        U: 'a + Clone + Send + Sync + ::std::fmt::Debug,
+        M: 'a + Fn(&T) -> U + Send + Sync,
+        F: FnOnce(&mut Context<'a, U>),
+        T: 'a,
+    {
+        let header = ContextHeader::new(ContextLabel::Context, name);
+        let mut child = Context::new(Some(header));
+        child.path = self.path.iter().chain(&child.path).cloned().collect();
+        child.serial = self.serial;
+        body(&mut child);
+        self.blocks
+            .push(Block::Mapped(MappedContext::new(map, child)))
+    }
+
     fn context_internal<F>(&mut self, header: Option<ContextHeader>, body: F)
     where
-        F: FnOnce(&mut Context<T>),
+        F: FnOnce(&mut Context<'a, T>),
         T: ::std::fmt::Debug,
     {
         let mut child = Context::new(header);
+        child.path = self.path.iter().chain(&child.path).cloned().collect();
+        child.shared_examples = self.shared_examples.clone();
+        child.serial = self.serial;
         body(&mut child);
         self.blocks.push(Block::Context(child))
     }
 
+    fn push_example(&mut self, mut example: Example<'a, T>) {
+        example.path = self.path.iter().chain(&example.path).cloned().collect();
+        self.blocks.push(Block::Example(example))
+    }
+
     /// Open and name a new example within the current context.
     ///
     /// Note that the order of execution **IS NOT** guaranteed to match the declaration order.
@@ -240,67 +662,307 @@ where
     ///
     /// - [`it`](struct.Context.html#method.it).
     /// - [`then`](struct.Context.html#method.then).
-    pub fn example<F, U>(&mut self, name: &'static str, body: F)
+    #[track_caller]
+    pub fn example<N, F, U>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        let header =
+            ExampleHeader::new(ExampleLabel::Example, name).with_location(Location::caller());
+        self.example_internal(header, body)
+    }
+
+    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`it`](struct.Context.html#method.it).
+    #[track_caller]
+    pub fn it<N, F, U>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        let header = ExampleHeader::new(ExampleLabel::It, name).with_location(Location::caller());
+        self.example_internal(header, body)
+    }
+
+    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
+    ///
+    /// Available further aliases:
+    ///
+    /// - [`it`](struct.Context.html#method.it).
+    #[track_caller]
+    pub fn then<N, F, U>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        let header = ExampleHeader::new(ExampleLabel::Then, name).with_location(Location::caller());
+        self.example_internal(header, body)
+    }
+
+    /// Register an example within the current context, if the condition holds
+    /// (see [`context_if`](struct.Context.html#method.context_if)).
+    #[track_caller]
+    pub fn example_if<N, F, U>(&mut self, condition: bool, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        if condition {
+            self.example(name, body);
+        }
+    }
+
+    fn example_internal<F, U>(&mut self, header: ExampleHeader, body: F)
+    where
+        F: 'a + Fn(&T) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        let example = Example::new(header, move |environment| {
+            catch_failure(|| body(environment).into_example_result())
+        });
+        self.push_example(example)
+    }
+
+    /// Declares an example per file of the given directory, named after the file
+    /// and passed its contents, e.g. for checking a parser against a set of fixtures.
+    ///
+    /// The directory gets read right away (i.e. when declaring the suite), its files in
+    /// alphabetical order. If it can't be read, a single failing example gets declared instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate rspec;
+    /// #
+    /// # pub fn main() {
+    /// rspec::run(&rspec::suite("a parser", (), |ctx| {
+    ///     ctx.examples_from_dir("tests/fixtures", |_env, contents| {
+    ///         !contents.is_empty()
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn examples_from_dir<P, F, U>(&mut self, path: P, body: F)
+    where
+        P: AsRef<Path>,
+        F: 'a + Fn(&T, &str) -> U + Send + Sync,
+        U: IntoExampleResult,
+    {
+        let location = Location::caller();
+        let path = path.as_ref();
+        let entries = fs::read_dir(path).and_then(|entries| {
+            let mut paths = vec![];
+            for entry in entries {
+                let path = entry?.path();
+                if path.is_file() {
+                    paths.push(path);
+                }
+            }
+            paths.sort();
+            Ok(paths)
+        });
+        let paths = match entries {
+            Ok(paths) => paths,
+            Err(error) => {
+                let name = path.display().to_string();
+                let header =
+                    ExampleHeader::new(ExampleLabel::Example, name).with_location(location);
+                let message = format!("could not read directory {}: {}", path.display(), error);
+                let example = Example::new(header, move |_| {
+                    ExampleResult::failure(Some(message.clone()))
+                });
+                return self.push_example(example);
+            }
+        };
+        let body = Arc::new(body);
+        for path in paths {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            let header = ExampleHeader::new(ExampleLabel::Example, name).with_location(location);
+            let example = match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    let body = body.clone();
+                    Example::new(header, move |environment| {
+                        catch_failure(|| body(environment, &contents).into_example_result())
+                    })
+                }
+                Err(error) => {
+                    let message = format!("could not read file {}: {}", path.display(), error);
+                    Example::new(header, move |_| {
+                        ExampleResult::failure(Some(message.clone()))
+                    })
+                }
+            };
+            self.push_example(example);
+        }
+    }
+
+    /// Open and name a new example within the current context, whose body is asynchronous.
+    ///
+    /// The runner itself stays synchronous: the returned future gets blocked on
+    /// (on the thread evaluating the example) and its output converted into the example's result.
+    ///
+    /// Only available with the `futures` feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.async_example("an example", |_env| async move {
+    ///         let value = fetch_value().await;
+    ///         value == 42
+    ///     });
+    /// }));
+    /// ```
+    #[cfg(feature = "futures")]
+    #[track_caller]
+    pub fn async_example<N, F, Fut>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> Fut + Send + Sync,
+        Fut: Future,
+        Fut::Output: IntoExampleResult,
+    {
+        let header =
+            ExampleHeader::new(ExampleLabel::Example, name).with_location(Location::caller());
+        self.example_internal(header, move |environment| {
+            futures_executor::block_on(body(environment)).into_example_result()
+        })
+    }
+
+    /// Open and name a new example within the current context, which is expected to panic.
+    ///
+    /// Much like `#[should_panic]` the example passes if its closure panics
+    /// (with a message containing `expected`, if provided) and fails otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", vec![1, 2, 3], |ctx| {
+    ///     ctx.example_should_panic("an example", Some("out of bounds"), |env| {
+    ///         env[42];
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn example_should_panic<N, F, U>(
+        &mut self,
+        name: N,
+        expected: Option<&'static str>,
+        body: F,
+    ) where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
+    {
+        let header =
+            ExampleHeader::new(ExampleLabel::Example, name).with_location(Location::caller());
+        let example = Example::new(header, move |environment| {
+            expect_panic(
+                || {
+                    body(environment);
+                },
+                expected,
+            )
+        });
+        self.push_example(example)
+    }
+
+    /// Open and name a new example within the current context, which is ignored
+    /// for the given reason rather than run (and so are the context's hooks for it).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.xit("an example", "not implemented yet", |_env| {
+    ///         unimplemented!()
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn xit<N, R, F, U>(&mut self, name: N, reason: R, _body: F)
     where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
+        N: Into<Cow<'static, str>>,
+        R: Into<String>,
+        F: 'a + Fn(&T) -> U + Send + Sync,
     {
-        let header = ExampleHeader::new(ExampleLabel::Example, name);
-        self.example_internal(header, body)
+        let header = ExampleHeader::new(ExampleLabel::It, name).with_location(Location::caller());
+        self.push_example(Example::ignored(header, reason.into()))
     }
 
-    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
-    ///
-    /// Available further aliases:
+    /// Open and name a new example within the current context,
+    /// which gets passed its very own mutable clone of the environment.
     ///
-    /// - [`it`](struct.Context.html#method.it).
-    pub fn it<F, U>(&mut self, name: &'static str, body: F)
-    where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
-    {
-        let header = ExampleHeader::new(ExampleLabel::It, name);
-        self.example_internal(header, body)
-    }
-
-    /// Alias for [`example`](struct.Context.html#method.example), see for more info.
+    /// Mutations of the environment are thus not observed by any other example
+    /// (nor by the context's `after_each` blocks), making this safe for parallel execution.
     ///
-    /// Available further aliases:
+    /// # Examples
     ///
-    /// - [`it`](struct.Context.html#method.it).
-    pub fn then<F, U>(&mut self, name: &'static str, body: F)
-    where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
-    {
-        let header = ExampleHeader::new(ExampleLabel::Then, name);
-        self.example_internal(header, body)
-    }
-
-    fn example_internal<F, U>(&mut self, header: ExampleHeader, body: F)
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", vec![], |ctx| {
+    ///     ctx.example_mut("an example", |env: &mut Vec<usize>| {
+    ///         env.push(42);
+    ///         env.len() == 1
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn example_mut<N, F, U>(&mut self, name: N, body: F)
     where
-        F: 'static + Fn(&T) -> U,
-        U: Into<ExampleResult>,
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&mut T) -> U + Send + Sync,
+        U: IntoExampleResult,
     {
-        use std::panic::{catch_unwind, AssertUnwindSafe};
-
-        let example = Example::new(header, move |environment| {
-            let result = catch_unwind(AssertUnwindSafe(|| body(&environment).into()));
-            match result {
-                Ok(result) => result,
-                Err(error) => {
-                    use std::borrow::Cow;
-                    let error_as_str = error.downcast_ref::<&str>().map(|s| Cow::from(*s));
-                    let error_as_string =
-                        error.downcast_ref::<String>().map(|s| Cow::from(s.clone()));
-                    let message = error_as_str
-                        .or(error_as_string)
-                        .map(|cow| format!("thread panicked at '{:?}'.", cow.to_string()));
-                    ExampleResult::Failure(message)
-                }
-            }
+        let header =
+            ExampleHeader::new(ExampleLabel::Example, name).with_location(Location::caller());
+        let example = Example::new_mut(header, move |environment| {
+            catch_failure(|| body(environment).into_example_result())
         });
-        self.blocks.push(Block::Example(example))
+        self.push_example(example)
     }
 
     /// Declares a closure that will be executed once before any
@@ -353,15 +1015,15 @@ where
     /// - [`before`](struct.Context.html#method.before).
     pub fn before_all<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
     {
-        self.before_all.push(Box::new(body))
+        self.before_all.push(Arc::new(body))
     }
 
     /// Alias for [`before_all`](struct.Context.html#method.before_all), see for more info.
     pub fn before<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
     {
         self.before_all(body)
     }
@@ -412,9 +1074,52 @@ where
     /// ```
     pub fn before_each<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
+    {
+        self.before_each.push(Arc::new(move |environment| {
+            body(environment);
+            None
+        }))
+    }
+
+    /// Declares a closure that will be executed once before each of the context's children
+    /// (context or example blocks), which may skip the child by returning a reason for doing so.
+    ///
+    /// A skipped child's examples get reported as ignored for the given reason,
+    /// without running any of the remaining `before_each` blocks, nor the child itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.before_each_or_skip(|_env| {
+    ///         if std::env::var("DATABASE_URL").is_err() {
+    ///             return Some("no database available".to_owned());
+    ///         }
+    ///         None
+    ///     });
+    ///
+    ///     ctx.example("an example", |_env| {
+    ///         // …
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn before_each_or_skip<F>(&mut self, body: F)
+    where
+        F: 'a + Fn(&mut T) -> Option<SkipReason> + Send + Sync,
     {
-        self.before_each.push(Box::new(body))
+        self.before_each.push(Arc::new(body))
     }
 
     /// Declares a closure that will be executed once after any
@@ -467,19 +1172,98 @@ where
     /// - [`after`](struct.Context.html#method.after).
     pub fn after_all<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
     {
-        self.after_all.push(Box::new(body))
+        self.after_all.push(Arc::new(body))
     }
 
     /// Alias for [`after_all`](struct.Context.html#method.after_all), see for more info.
     pub fn after<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
     {
         self.after_all(body)
     }
 
+    /// Open a new name-less context within the current context, whose examples get passed
+    /// a resource alongside the current context's environment (i.e. `&(env, resource)`).
+    ///
+    /// The resource gets built by `setup` once before any of the context's blocks are being
+    /// executed, and handed to `teardown` once after all of them have been executed,
+    /// regardless of the blocks being executed in parallel (e.g. for spinning up a server).
+    ///
+    /// As examples may hold on to clones of the resource beyond their execution,
+    /// `teardown` gets passed a reference to it, rather than the resource itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", (), |ctx| {
+    ///     ctx.with_resource(
+    ///         |_env| vec!["a", "server"],
+    ///         |ctx| {
+    ///             ctx.example("an example", |(_, server)| server.len() == 2);
+    ///         },
+    ///         |_server| {
+    ///             // …
+    ///         },
+    ///     );
+    /// }));
+    /// # }
+    /// ```
+    pub fn with_resource<R, S, F, D>(&mut self, setup: S, body: F, teardown: D)
+    where
+        R: 'a + Send + Sync + ::std::fmt::Debug,
+        S: 'a + Fn(&T) -> R + Send + Sync,
+        F: FnOnce(&mut Context<'a, (T, SharedEnv<R>)>),
+        D: 'a + Fn(&R) + Send + Sync,
+        T: 'a + Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let resource: Arc<Mutex<Option<SharedEnv<R>>>> = Arc::new(Mutex::new(None));
+        let (set_up, torn_down, mapped) = (resource.clone(), resource.clone(), resource);
+        let mut child = Context::new(None);
+        child.path = self.path.clone();
+        child.serial = self.serial;
+        body(&mut child);
+        self.context_internal(None, move |ctx| {
+            ctx.before_all(move |environment| {
+                let resource = SharedEnv::new(setup(environment));
+                *set_up
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(resource);
+            });
+            ctx.after_all(move |_| {
+                let resource = torn_down
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take();
+                if let Some(resource) = resource {
+                    teardown(&resource);
+                }
+            });
+            let map = move |environment: &T| {
+                let resource = mapped
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .clone()
+                    .expect("resource to have been set up by `before_all`");
+                (environment.clone(), resource)
+            };
+            ctx.blocks
+                .push(Block::Mapped(MappedContext::new(map, child)));
+        })
+    }
+
     /// Declares a closure that will be executed once after each
     /// of the context's children (context or example blocks).
     ///
@@ -526,14 +1310,197 @@ where
     /// ```
     pub fn after_each<F>(&mut self, body: F)
     where
-        F: 'static + Fn(&mut T),
+        F: 'a + Fn(&mut T) + Send + Sync,
+    {
+        self.after_each.push(Arc::new(body))
+    }
+
+    /// Declares a lazily evaluated value, which gets built upon its first access
+    /// from within an example (or its `before_each`/`after_each` blocks) and is then
+    /// memoized for the remainder of said example.
+    ///
+    /// The values are stored in a [`Lets`](struct.Lets.html) map,
+    /// which the environment provides access to via `AsMut<Lets>`.
+    ///
+    /// Unlike the context's other blocks, `factory` (and the value it builds) can't borrow
+    /// from the enclosing scope: `Lets` stores its values as `Any` for retrieving them by type,
+    /// and has no lifetime parameter of its own for embedding it into environments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// #[derive(Clone, Debug, Default)]
+    /// struct Environment {
+    ///     lets: rspec::block::Lets,
+    /// }
+    ///
+    /// impl AsMut<rspec::block::Lets> for Environment {
+    ///     fn as_mut(&mut self) -> &mut rspec::block::Lets {
+    ///         &mut self.lets
+    ///     }
+    /// }
+    ///
+    /// runner.run(&rspec::suite("a test suite", Environment::default(), |ctx| {
+    ///     ctx.let_value("answer", || 42);
+    ///
+    ///     ctx.example("an example", |env| *env.lets.get::<i32>("answer") == 42);
+    /// }));
+    /// # }
+    /// ```
+    pub fn let_value<V, F>(&mut self, name: &'static str, factory: F)
+    where
+        T: AsMut<Lets>,
+        V: 'static + Send + Sync,
+        F: 'static + Fn() -> V + Send + Sync,
+    {
+        let factory = Arc::new(factory);
+        self.before_each(move |environment| {
+            environment.as_mut().define(name, factory.clone());
+        })
+    }
+
+    /// Declares the context's subject, i.e. the thing under test, which (like a value declared via
+    /// [`let_value`](struct.Context.html#method.let_value)) gets built upon its first access
+    /// and is then memoized for the remainder of the current example.
+    ///
+    /// Examples access it via [`Lets::subject`](struct.Lets.html#method.subject).
+    /// Like for `let_value`, `factory` can't borrow from the enclosing scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # pub fn main() {
+    /// #[derive(Clone, Debug, Default)]
+    /// struct Environment {
+    ///     lets: rspec::block::Lets,
+    /// }
+    ///
+    /// impl AsMut<rspec::block::Lets> for Environment {
+    ///     fn as_mut(&mut self) -> &mut rspec::block::Lets {
+    ///         &mut self.lets
+    ///     }
+    /// }
+    ///
+    /// rspec::run(&rspec::describe("a stack", Environment::default(), |ctx| {
+    ///     ctx.subject(|| vec![1, 2, 3]);
+    ///
+    ///     ctx.it("has a top", |env| env.lets.subject::<Vec<i32>>().last() == Some(&3));
+    /// }));
+    /// # }
+    /// ```
+    pub fn subject<S, F>(&mut self, factory: F)
+    where
+        T: AsMut<Lets>,
+        S: 'static + Send + Sync,
+        F: 'static + Fn() -> S + Send + Sync,
+    {
+        self.let_value(SUBJECT, factory)
+    }
+
+    /// Declares a closure that wraps each of the context's children (context or example blocks),
+    /// including their `before_each`/`after_each` blocks.
+    ///
+    /// The closure gets passed a [`RunExample`](struct.RunExample.html) handle,
+    /// which it needs to call for the wrapped block to be run at all.
+    /// Blocks whose handle doesn't get called are reported as ignored.
+    ///
+    /// Multiple `around` blocks are nested in the order of their declaration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use std::io;
+    /// # use std::sync::Arc;
+    /// #
+    /// # pub fn main() {
+    /// #     let logger = Arc::new(rspec::Logger::new(io::stdout()));
+    /// #     let configuration = rspec::ConfigurationBuilder::default().build().unwrap();
+    /// #     let runner = rspec::Runner::new(configuration, vec![logger]);
+    /// #
+    /// runner.run(&rspec::suite("a test suite", false, |ctx| {
+    ///     ctx.around(|in_transaction, run| {
+    ///         *in_transaction = true;
+    ///         run.call(in_transaction);
+    ///         *in_transaction = false;
+    ///     });
+    ///
+    ///     ctx.example("an example", |in_transaction| *in_transaction);
+    /// }));
+    /// # }
+    /// ```
+    pub fn around<F>(&mut self, body: F)
+    where
+        F: 'a + Fn(&mut T, RunExample<T>) + Send + Sync,
     {
-        self.after_each.push(Box::new(body))
+        self.around.push(Arc::new(body))
+    }
+}
+
+/// Evaluates an example's closure, turning any panic into a failure.
+pub(crate) fn catch_failure<F>(body: F) -> ExampleResult
+where
+    F: FnOnce() -> ExampleResult,
+{
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(result) => result,
+        Err(error) => {
+            let message =
+                panic_message(&*error).map(|message| format!("thread panicked at '{}'.", message));
+            ExampleResult::failure(message)
+        }
+    }
+}
+
+/// Evaluates an example's closure, turning any panic (containing `expected`, if provided)
+/// into a success and anything else into a failure.
+fn expect_panic<F>(body: F, expected: Option<&str>) -> ExampleResult
+where
+    F: FnOnce(),
+{
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(()) => ExampleResult::failure(Some("example did not panic as expected".to_owned())),
+        Err(error) => {
+            let message = panic_message(&*error).unwrap_or_default();
+            match expected {
+                Some(expected) if !message.contains(expected) => ExampleResult::failure(Some(
+                    format!(
+                        "panic did not contain expected string\n  panic message: {:?}\n  expected substring: {:?}",
+                        message, expected
+                    ),
+                )),
+                _ => ExampleResult::Success,
+            }
+        }
     }
 }
 
+/// Extracts the message of a panic's payload, if it is a string.
+pub(crate) fn panic_message(error: &(dyn Any + Send)) -> Option<String> {
+    let error_as_str = error.downcast_ref::<&str>().map(|s| (*s).to_owned());
+    let error_as_string = error.downcast_ref::<String>().cloned();
+    error_as_str.or(error_as_string)
+}
+
 #[cfg(test)]
-impl<T> Default for Context<T> {
+impl<'a, T> Default for Context<'a, T> {
     /// Used for testing
     fn default() -> Self {
         Context::new(None)
@@ -542,7 +1509,8 @@ impl<T> Default for Context<T> {
 
 #[cfg(test)]
 mod tests {
-    use block::{describe, given, suite};
+    use block::{describe, given, suite, Block, SharedExamples};
+    use header::ContextLabel;
 
     macro_rules! test_suite_alias {
         ($suite: ident) => {
@@ -550,6 +1518,23 @@ mod tests {
         };
     }
 
+    #[test]
+    fn it_records_the_location_of_examples() {
+        // arrange
+        let expected_line = line!() + 3;
+        // act
+        let suite = suite("suite", (), |ctx| {
+            ctx.it("example", |_| {});
+        });
+        // assert
+        let location = match suite.context.blocks[0] {
+            Block::Example(ref example) => example.header.location().unwrap(),
+            _ => panic!("expected an example"),
+        };
+        assert!(location.file().ends_with("context.rs"));
+        assert_eq!(expected_line, location.line());
+    }
+
     #[test]
     fn it_has_root_functions() {
         test_suite_alias!(suite);
@@ -579,6 +1564,288 @@ mod tests {
         test_context_alias!(given, specify);
     }
 
+    #[test]
+    fn it_has_contexual_function_given() {
+        test_context_alias!(suite, given);
+        test_context_alias!(describe, given);
+        test_context_alias!(given, given);
+    }
+
+    #[test]
+    fn it_labels_given_contexts() {
+        // arrange
+        let suite = given("suite", (), |ctx| {
+            ctx.given("context", |ctx| {
+                ctx.when("context", |ctx| {
+                    ctx.then("example", |_| {});
+                });
+            });
+        });
+        // act
+        let header = match suite.context.blocks[0] {
+            Block::Context(ref context) => context.header.clone().unwrap(),
+            _ => panic!("expected a context"),
+        };
+        // assert
+        assert_eq!(ContextLabel::Given, header.label);
+        assert_eq!("Given \"context\"", header.to_string());
+    }
+
+    #[test]
+    fn it_renders_contexts_with_custom_labels() {
+        // arrange
+        let suite = suite("suite", (), |ctx| {
+            ctx.context_as("Scenario", "context", |ctx| {
+                ctx.example("example", |_| {});
+            });
+        });
+        // act
+        let header = match suite.context.blocks[0] {
+            Block::Context(ref context) => context.header.clone().unwrap(),
+            _ => panic!("expected a context"),
+        };
+        // assert
+        assert_eq!(ContextLabel::Context, header.label);
+        assert_eq!("Scenario \"context\"", header.to_string());
+    }
+
+    #[test]
+    fn it_registers_conditional_blocks_only_if_the_condition_holds() {
+        // arrange
+        let expected_line = line!() + 9;
+        // act
+        let suite = suite("suite", (), |ctx| {
+            ctx.context_if(false, "excluded", |ctx| {
+                ctx.example("example", |_| {});
+                ctx.example("another example", |_| {});
+            });
+            ctx.context_if(true, "included", |ctx| {
+                ctx.example_if(false, "excluded", |_| {});
+                ctx.example_if(true, "included", |_| {});
+            });
+        });
+        // assert
+        assert_eq!(1, suite.num_examples());
+        assert_eq!(1, suite.context.num_blocks());
+        assert!(suite.context.contains_example(&["included", "included"]));
+        let location = match suite.context.blocks[0] {
+            Block::Context(ref context) => match context.blocks[0] {
+                Block::Example(ref example) => example.header.location().unwrap(),
+                _ => panic!("expected an example"),
+            },
+            _ => panic!("expected a context"),
+        };
+        assert_eq!(expected_line, location.line());
+    }
+
+    #[test]
+    fn it_includes_shared_examples_by_name() {
+        // arrange
+        let suite = suite("suite", (), |ctx| {
+            ctx.shared_examples("a group", |ctx| {
+                ctx.example("shared", |_| {});
+            });
+            ctx.context("first", |ctx| ctx.behaves_like("a group"));
+            ctx.context("second", |ctx| ctx.it_behaves_like("a group"));
+        });
+        // act
+        let count = suite.num_examples();
+        // assert
+        assert_eq!(2, count);
+        assert!(suite.context.contains_example(&["first", "shared"]));
+        assert!(suite.context.contains_example(&["second", "shared"]));
+    }
+
+    #[test]
+    fn it_includes_shared_examples_of_a_registry() {
+        // arrange
+        let mut shared = SharedExamples::new();
+        shared.register("a group", |ctx| ctx.example("shared", |_| {}));
+        // act
+        let suite = suite("suite", (), |ctx| {
+            ctx.use_shared_examples(&shared);
+            ctx.context("context", |ctx| ctx.behaves_like("a group"));
+        });
+        // assert
+        assert!(suite.context.contains_example(&["context", "shared"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "no shared examples registered as \"unknown\"")]
+    fn it_panics_on_unknown_shared_examples() {
+        suite("suite", (), |ctx| ctx.behaves_like("unknown"));
+    }
+
+    mod examples_from_dir {
+        use super::*;
+
+        use std::env;
+        use std::fs;
+        use std::process;
+
+        use report::{ExampleResult, Report};
+        use runner::{ConfigurationBuilder, Runner};
+
+        #[test]
+        fn it_declares_an_example_per_file() {
+            // arrange
+            let dir = env::temp_dir().join(format!("rspec-fixtures-{}", process::id()));
+            fs::create_dir_all(dir.join("a subdirectory")).unwrap();
+            fs::write(dir.join("valid.txt"), "42").unwrap();
+            fs::write(dir.join("invalid.txt"), "forty-two").unwrap();
+            // act
+            let suite = suite("suite", (), |ctx| {
+                ctx.examples_from_dir(&dir, |_, contents| contents.parse::<u32>().is_ok());
+            });
+            // assert
+            let _ = fs::remove_dir_all(&dir);
+            assert_eq!(2, suite.num_examples());
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let report = Runner::new(config, vec![]).run(&suite);
+            let result = report.get_context().find_example(&["valid.txt"]).unwrap();
+            assert_eq!(&ExampleResult::Success, result.get_result());
+            let result = report.get_context().find_example(&["invalid.txt"]).unwrap();
+            assert!(result.is_failure());
+        }
+
+        #[test]
+        fn it_declares_a_failing_example_for_missing_directories() {
+            // arrange
+            let dir = env::temp_dir().join("rspec-no-such-directory");
+            // act
+            let suite = suite("suite", (), |ctx| {
+                ctx.examples_from_dir(&dir, |_, _| true);
+            });
+            // assert
+            assert_eq!(1, suite.num_examples());
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let report = Runner::new(config, vec![]).run(&suite);
+            assert_eq!(1, report.get_failed());
+        }
+    }
+
+    mod catch_failure {
+        use block::catch_failure;
+        use report::ExampleResult;
+
+        #[test]
+        fn it_renders_panic_messages_verbatim() {
+            // arrange
+            let body = || -> ExampleResult { panic!("expected \"a\",\nfound \"b\"") };
+            // act
+            let result = catch_failure(body);
+            // assert
+            let expected = "thread panicked at 'expected \"a\",\nfound \"b\"'.";
+            assert_eq!(ExampleResult::failure(Some(expected.to_owned())), result);
+        }
+    }
+
+    mod expect_panic {
+        use block::context::expect_panic;
+        use report::ExampleResult;
+
+        #[test]
+        fn it_aligns_the_lines_of_its_failure_message() {
+            // arrange
+            let body = || panic!("out of bounds");
+            // act
+            let result = expect_panic(body, Some("overflow"));
+            // assert
+            let expected = "panic did not contain expected string\n  panic message: \"out of bounds\"\n  expected substring: \"overflow\"";
+            assert_eq!(ExampleResult::failure(Some(expected.to_owned())), result);
+        }
+    }
+
+    mod with_resource {
+        use super::*;
+
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use report::Report;
+        use runner::{ConfigurationBuilder, Runner};
+
+        #[test]
+        fn it_sets_up_and_tears_down_the_resource_once() {
+            // arrange
+            let setups = Arc::new(AtomicUsize::new(0));
+            let teardowns = Arc::new(AtomicUsize::new(0));
+            let (counted_setups, counted_teardowns) = (setups.clone(), teardowns.clone());
+            let suite = suite("suite", 2, move |ctx| {
+                ctx.with_resource(
+                    move |env| {
+                        counted_setups.fetch_add(1, Ordering::SeqCst);
+                        vec![*env; 3]
+                    },
+                    |ctx| {
+                        for _ in 0..4 {
+                            ctx.it("sees the resource", |&(env, ref resource)| {
+                                resource.len() == 3 && resource[0] == env
+                            });
+                        }
+                        ctx.context("nested", |ctx| {
+                            ctx.it("sees it too", |(_, resource)| resource.len() == 3);
+                        });
+                    },
+                    move |resource| {
+                        assert_eq!(&vec![2; 3], resource);
+                        counted_teardowns.fetch_add(1, Ordering::SeqCst);
+                    },
+                );
+            });
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            // act
+            let report = Runner::new(config, vec![]).run(&suite);
+            // assert
+            assert_eq!(5, report.get_passed());
+            assert_eq!(1, setups.load(Ordering::SeqCst));
+            assert_eq!(1, teardowns.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_tears_down_the_resource_even_if_examples_kept_clones_of_it() {
+            use std::sync::Mutex;
+            // arrange
+            let teardowns = Arc::new(AtomicUsize::new(0));
+            let kept = Arc::new(Mutex::new(vec![]));
+            let (counted_teardowns, keeping) = (teardowns.clone(), kept.clone());
+            let suite = suite("suite", (), move |ctx| {
+                ctx.with_resource(
+                    |_| vec!["a", "server"],
+                    move |ctx| {
+                        ctx.it("keeps the resource", move |(_, resource)| {
+                            keeping.lock().unwrap().push(resource.clone());
+                        });
+                    },
+                    move |resource| {
+                        assert_eq!(&vec!["a", "server"], resource);
+                        counted_teardowns.fetch_add(1, Ordering::SeqCst);
+                    },
+                );
+            });
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            // act
+            let report = Runner::new(config, vec![]).run(&suite);
+            // assert
+            assert!(report.is_success());
+            assert_eq!(1, kept.lock().unwrap().len());
+            assert_eq!(1, teardowns.load(Ordering::SeqCst));
+        }
+    }
+
     #[test]
     fn it_has_contexual_function_when() {
         test_context_alias!(suite, when);
@@ -609,6 +1876,10 @@ mod tests {
         test_example_alias!(given, context, example);
         test_example_alias!(given, specify, example);
         test_example_alias!(given, when, example);
+
+        test_example_alias!(suite, given, example);
+        test_example_alias!(describe, given, example);
+        test_example_alias!(given, given, example);
     }
 
     #[test]
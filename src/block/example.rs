@@ -1,23 +1,87 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
 use header::ExampleHeader;
 use report::ExampleResult;
 
+/// The assertion closure of an [`Example`](struct.Example.html).
+pub(crate) enum ExampleFunction<'a, T> {
+    /// Gets passed a shared reference to the environment.
+    Immutable(Arc<dyn Fn(&T) -> ExampleResult + Send + Sync + 'a>),
+    /// Gets passed a mutable reference to its very own clone of the environment.
+    Mutable(Arc<dyn Fn(&mut T) -> ExampleResult + Send + Sync + 'a>),
+}
+
+impl<'a, T> Clone for ExampleFunction<'a, T> {
+    fn clone(&self) -> Self {
+        match self {
+            ExampleFunction::Immutable(ref function) => {
+                ExampleFunction::Immutable(function.clone())
+            }
+            ExampleFunction::Mutable(ref function) => ExampleFunction::Mutable(function.clone()),
+        }
+    }
+}
+
 /// Test examples are the smallest unit of a testing framework, wrapping one or more assertions.
-pub struct Example<T> {
+pub struct Example<'a, T> {
     pub(crate) header: ExampleHeader,
-    pub(crate) function: Box<dyn Fn(&T) -> ExampleResult>,
+    /// Names of the enclosing (named) contexts, followed by the example's name.
+    pub(crate) path: Vec<Cow<'static, str>>,
+    pub(crate) function: ExampleFunction<'a, T>,
+    /// The reason for ignoring the example (along with its hooks) rather than running it, if any.
+    pub(crate) ignored: Option<String>,
 }
 
-impl<T> Example<T> {
+impl<'a, T> Clone for Example<'a, T> {
+    fn clone(&self) -> Self {
+        Example {
+            header: self.header.clone(),
+            path: self.path.clone(),
+            function: self.function.clone(),
+            ignored: self.ignored.clone(),
+        }
+    }
+}
+
+impl<'a, T> Example<'a, T> {
     pub(crate) fn new<F>(header: ExampleHeader, assertion: F) -> Self
     where
-        F: 'static + Fn(&T) -> ExampleResult,
+        F: 'a + Fn(&T) -> ExampleResult + Send + Sync,
+    {
+        Example {
+            path: vec![header.name.clone()],
+            header,
+            function: ExampleFunction::Immutable(Arc::new(assertion)),
+            ignored: None,
+        }
+    }
+
+    pub(crate) fn new_mut<F>(header: ExampleHeader, assertion: F) -> Self
+    where
+        F: 'a + Fn(&mut T) -> ExampleResult + Send + Sync,
     {
         Example {
+            path: vec![header.name.clone()],
             header,
-            function: Box::new(assertion),
+            function: ExampleFunction::Mutable(Arc::new(assertion)),
+            ignored: None,
         }
     }
 
+    pub(crate) fn ignored(header: ExampleHeader, reason: String) -> Self {
+        Example {
+            ignored: Some(reason),
+            ..Example::new(header, |_| ExampleResult::Ignored(None))
+        }
+    }
+
+    /// Whether the example's path passes `predicate`.
+    pub(crate) fn is_matching(&self, predicate: &dyn Fn(&[&str]) -> bool) -> bool {
+        let path: Vec<&str> = self.path.iter().map(AsRef::as_ref).collect();
+        predicate(&path)
+    }
+
     /// Used for testing purpose
     #[cfg(test)]
     pub fn fixture_success() -> Self {
@@ -27,15 +91,12 @@ impl<T> Example<T> {
     /// Used for testing purpose
     #[cfg(test)]
     pub fn fixture_ignored() -> Self {
-        Example::new(ExampleHeader::default(), |_| ExampleResult::Ignored)
+        Example::new(ExampleHeader::default(), |_| ExampleResult::Ignored(None))
     }
 
     /// Used for testing purpose
     #[cfg(test)]
     pub fn fixture_failed() -> Self {
-        Example::new(ExampleHeader::default(), |_| ExampleResult::Failure(None))
+        Example::new(ExampleHeader::default(), |_| ExampleResult::failure(None))
     }
 }
-
-unsafe impl<T> Send for Example<T> where T: Send {}
-unsafe impl<T> Sync for Example<T> where T: Sync {}
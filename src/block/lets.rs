@@ -0,0 +1,197 @@
+//! Lazily evaluated, memoized values for use within examples, see
+//! [`Context::let_value`](../struct.Context.html#method.let_value).
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type Value = Arc<dyn Any + Send + Sync>;
+type Factory = Arc<dyn Fn() -> Value + Send + Sync>;
+
+/// The name the subject is declared under, see [`Lets::subject`](struct.Lets.html#method.subject).
+pub(crate) const SUBJECT: &str = "subject";
+
+/// A typed map of lazily evaluated values, to be embedded into a suite's environment.
+///
+/// Each value gets built upon first access and is then memoized for the remainder
+/// of the current example. Cloning a `Lets` (as the runner does for each example)
+/// keeps the factories, but drops any memoized values.
+#[derive(Default)]
+pub struct Lets {
+    factories: HashMap<&'static str, Factory>,
+    values: Mutex<HashMap<&'static str, Value>>,
+}
+
+impl Lets {
+    pub(crate) fn define<V, F>(&mut self, name: &'static str, factory: Arc<F>)
+    where
+        V: 'static + Send + Sync,
+        F: 'static + Fn() -> V + Send + Sync,
+    {
+        let factory: Factory = Arc::new(move || Arc::new(factory()) as Value);
+        self.factories.insert(name, factory);
+        self.values().remove(name);
+    }
+
+    /// Returns the value named `name`, building it first if it hasn't been accessed yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value of type `V` has been declared under the given name.
+    pub fn get<V>(&self, name: &'static str) -> Arc<V>
+    where
+        V: 'static + Send + Sync,
+    {
+        let value = self
+            .values()
+            .entry(name)
+            .or_insert_with(|| {
+                let factory = self
+                    .factories
+                    .get(name)
+                    .unwrap_or_else(|| panic!("no value named {:?} has been declared", name));
+                factory()
+            })
+            .clone();
+        value
+            .downcast::<V>()
+            .unwrap_or_else(|_| panic!("value named {:?} is of a different type", name))
+    }
+
+    /// Returns the subject, building it first if it hasn't been accessed yet,
+    /// see [`Context::subject`](../struct.Context.html#method.subject).
+    ///
+    /// # Panics
+    ///
+    /// Panics if no subject of type `S` has been declared.
+    pub fn subject<S>(&self) -> Arc<S>
+    where
+        S: 'static + Send + Sync,
+    {
+        self.get(SUBJECT)
+    }
+
+    fn values(&self) -> MutexGuard<'_, HashMap<&'static str, Value>> {
+        self.values
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clone for Lets {
+    fn clone(&self) -> Self {
+        Lets {
+            factories: self.factories.clone(),
+            values: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl fmt::Debug for Lets {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.factories.keys()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use runner::{ConfigurationBuilder, Runner};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Debug, Default)]
+    struct Environment {
+        lets: Lets,
+    }
+
+    impl AsMut<Lets> for Environment {
+        fn as_mut(&mut self) -> &mut Lets {
+            &mut self.lets
+        }
+    }
+
+    #[test]
+    fn it_memoizes_values_per_example() {
+        // arrange
+        let calls = Arc::new(AtomicUsize::new(0));
+        let factory_calls = calls.clone();
+        let runner = Runner::new(
+            ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap(),
+            vec![],
+        );
+        let suite = suite("a suite", Environment::default(), |ctx| {
+            ctx.let_value("value", move || {
+                factory_calls.fetch_add(1, Ordering::SeqCst)
+            });
+            ctx.example("accesses it once", |env| {
+                env.lets.get::<usize>("value");
+            });
+            ctx.context("a context", |ctx| {
+                ctx.example("accesses it thrice", |env| {
+                    let value = env.lets.get::<usize>("value");
+                    value == env.lets.get::<usize>("value") && value == env.lets.get("value")
+                });
+            });
+        });
+        // act
+        let report = runner.try_run(&suite);
+        // assert
+        assert!(report.is_ok());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn it_memoizes_the_subject_per_example() {
+        // arrange
+        let runner = Runner::new(
+            ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap(),
+            vec![],
+        );
+        let suite = suite("a suite", Environment::default(), |ctx| {
+            ctx.subject(|| Mutex::new(vec![1, 2, 3]));
+            ctx.before_each(|env| {
+                env.lets
+                    .subject::<Mutex<Vec<i32>>>()
+                    .lock()
+                    .unwrap()
+                    .push(4);
+            });
+            ctx.example("first", |env| {
+                let subject = env.lets.subject::<Mutex<Vec<i32>>>();
+                Arc::ptr_eq(&subject, &env.lets.subject())
+                    && *subject.lock().unwrap() == vec![1, 2, 3, 4]
+            });
+            ctx.example("second", |env| {
+                *env.lets.subject::<Mutex<Vec<i32>>>().lock().unwrap() == vec![1, 2, 3, 4]
+            });
+        });
+        // act
+        let report = runner.try_run(&suite);
+        // assert
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn it_drops_memoized_values_when_cloned() {
+        let mut lets = Lets::default();
+        lets.define("value", Arc::new(|| vec![1, 2, 3]));
+        let value = lets.get::<Vec<i32>>("value");
+        assert!(Arc::ptr_eq(&value, &lets.get("value")));
+        assert!(!Arc::ptr_eq(&value, &lets.clone().get("value")));
+    }
+
+    #[test]
+    #[should_panic(expected = "no value named \"missing\"")]
+    fn it_panics_for_undeclared_values() {
+        Lets::default().get::<i32>("missing");
+    }
+}
@@ -0,0 +1,158 @@
+//! Contexts with an environment derived from their parent's, see
+//! [`Context::context_map`](struct.Context.html#method.context_map).
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use block::Context;
+use header::ContextHeader;
+use report::{BlockReport, ExampleResult};
+use runner::Runner;
+use visitor::TestSuiteVisitor;
+
+/// The type-erased operations on a context of some other environment type than its parent's.
+pub(crate) trait ErasedContext<'a, T>: Send + Sync {
+    fn header(&self) -> Option<&ContextHeader>;
+    fn num_examples(&self) -> usize;
+    fn num_contexts(&self) -> usize;
+    fn max_depth(&self) -> usize;
+    fn contains_example(&self, path: &[&str]) -> bool;
+    fn is_selected(&self, runner: &Runner) -> bool;
+    fn skip(&self, runner: &Runner, result: &ExampleResult) -> BlockReport;
+    fn visit(&self, runner: &Runner, environment: &T) -> BlockReport;
+    fn filtered(
+        &self,
+        predicate: &dyn Fn(&[&str]) -> bool,
+    ) -> Option<Box<dyn ErasedContext<'a, T> + 'a>>;
+    fn prefix_paths(&mut self, prefix: &[Cow<'static, str>]);
+}
+
+/// A context whose environment gets derived from its parent's environment of type `T`.
+pub struct MappedContext<'a, T> {
+    inner: Box<dyn ErasedContext<'a, T> + 'a>,
+}
+
+impl<'a, T> MappedContext<'a, T> {
+    pub(crate) fn new<U, M>(map: M, context: Context<'a, U>) -> Self
+    where
+        U: 'a + Clone + Send + Sync + fmt::Debug,
+        M: 'a + Fn(&T) -> U + Send + Sync,
+        T: 'a,
+    {
+        MappedContext {
+            inner: Box::new(Mapped {
+                map: Arc::new(map),
+                context,
+            }),
+        }
+    }
+
+    pub fn header(&self) -> Option<&ContextHeader> {
+        self.inner.header()
+    }
+
+    pub fn num_examples(&self) -> usize {
+        self.inner.num_examples()
+    }
+
+    pub fn num_contexts(&self) -> usize {
+        self.inner.num_contexts()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.inner.max_depth()
+    }
+
+    pub(crate) fn contains_example(&self, path: &[&str]) -> bool {
+        self.inner.contains_example(path)
+    }
+
+    pub(crate) fn is_selected(&self, runner: &Runner) -> bool {
+        self.inner.is_selected(runner)
+    }
+
+    pub(crate) fn skip(&self, runner: &Runner, result: &ExampleResult) -> BlockReport {
+        self.inner.skip(runner, result)
+    }
+
+    pub(crate) fn visit(&self, runner: &Runner, environment: &T) -> BlockReport {
+        self.inner.visit(runner, environment)
+    }
+
+    /// A copy of the context, containing only the examples whose path passes `predicate`
+    /// (or `None` if it would contain no examples at all).
+    pub(crate) fn filtered(&self, predicate: &dyn Fn(&[&str]) -> bool) -> Option<Self> {
+        self.inner
+            .filtered(predicate)
+            .map(|inner| MappedContext { inner })
+    }
+
+    pub(crate) fn prefix_paths(&mut self, prefix: &[Cow<'static, str>]) {
+        self.inner.prefix_paths(prefix)
+    }
+}
+
+struct Mapped<'a, U, M> {
+    map: Arc<M>,
+    context: Context<'a, U>,
+}
+
+impl<'a, T, U, M> ErasedContext<'a, T> for Mapped<'a, U, M>
+where
+    U: 'a + Clone + Send + Sync + fmt::Debug,
+    M: 'a + Fn(&T) -> U + Send + Sync,
+    T: 'a,
+{
+    fn header(&self) -> Option<&ContextHeader> {
+        self.context.header.as_ref()
+    }
+
+    fn num_examples(&self) -> usize {
+        self.context.num_examples()
+    }
+
+    fn num_contexts(&self) -> usize {
+        self.context.num_contexts()
+    }
+
+    fn max_depth(&self) -> usize {
+        self.context.max_depth()
+    }
+
+    fn contains_example(&self, path: &[&str]) -> bool {
+        self.context.contains_example(path)
+    }
+
+    fn is_selected(&self, runner: &Runner) -> bool {
+        runner.is_context_selected(&self.context)
+    }
+
+    fn skip(&self, runner: &Runner, result: &ExampleResult) -> BlockReport {
+        runner.skip_context(&self.context, result)
+    }
+
+    fn visit(&self, runner: &Runner, environment: &T) -> BlockReport {
+        let mut environment = (self.map)(environment);
+        let report = runner.visit(&self.context, &mut environment);
+        BlockReport::Context(self.context.header.clone(), report)
+    }
+
+    fn filtered(
+        &self,
+        predicate: &dyn Fn(&[&str]) -> bool,
+    ) -> Option<Box<dyn ErasedContext<'a, T> + 'a>> {
+        let context = self.context.filtered(predicate);
+        if context.num_examples() == 0 {
+            return None;
+        }
+        Some(Box::new(Mapped {
+            map: self.map.clone(),
+            context,
+        }))
+    }
+
+    fn prefix_paths(&mut self, prefix: &[Cow<'static, str>]) {
+        self.context.prefix_paths(prefix)
+    }
+}
@@ -2,26 +2,64 @@
 
 pub mod context;
 pub mod example;
+pub mod lets;
+pub mod mapped;
+pub mod shared;
+pub mod shared_examples;
 pub mod suite;
 
 pub use block::context::*;
 pub use block::example::*;
+pub use block::lets::*;
+pub use block::mapped::*;
+pub use block::shared::*;
+pub use block::shared_examples::*;
 pub use block::suite::*;
 
 /// Blocks are used to build a tree structure of named tests and contextes.
-pub enum Block<T> {
-    Context(Context<T>),
-    Example(Example<T>),
+pub enum Block<'a, T> {
+    Context(Context<'a, T>),
+    /// A context with an environment derived from its parent's.
+    Mapped(MappedContext<'a, T>),
+    Example(Example<'a, T>),
 }
 
-impl<T> Block<T> {
+impl<'a, T> Block<'a, T> {
     pub fn num_examples(&self) -> usize {
         match self {
             Block::Context(ref context) => context.num_examples(),
+            Block::Mapped(ref context) => context.num_examples(),
             Block::Example(_) => 1,
         }
     }
-}
 
-unsafe impl<T> Send for Block<T> where T: Send {}
-unsafe impl<T> Sync for Block<T> where T: Sync {}
+    pub fn num_contexts(&self) -> usize {
+        match self {
+            Block::Context(ref context) => 1 + context.num_contexts(),
+            Block::Mapped(ref context) => 1 + context.num_contexts(),
+            Block::Example(_) => 0,
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        match self {
+            Block::Context(ref context) => 1 + context.max_depth(),
+            Block::Mapped(ref context) => 1 + context.max_depth(),
+            Block::Example(_) => 0,
+        }
+    }
+
+    /// A copy of the block, containing only the examples whose path passes `predicate`
+    /// (or `None` if it would contain no examples at all).
+    pub(crate) fn filtered(&self, predicate: &dyn Fn(&[&str]) -> bool) -> Option<Block<'a, T>> {
+        match self {
+            Block::Context(ref context) => Some(context.filtered(predicate))
+                .filter(|context| context.num_examples() > 0)
+                .map(Block::Context),
+            Block::Mapped(ref context) => context.filtered(predicate).map(Block::Mapped),
+            Block::Example(ref example) => Some(example.clone())
+                .filter(|example| example.is_matching(predicate))
+                .map(Block::Example),
+        }
+    }
+}
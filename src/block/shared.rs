@@ -0,0 +1,137 @@
+//! Cheaply clonable, read-only environment fields, see [`SharedEnv`](struct.SharedEnv.html).
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A read-only value shared by all clones of a suite's environment.
+///
+/// The runner hands each example its own clone of the environment. For expensive,
+/// immutable setups (e.g. a parsed fixture) this deep-cloning is wasteful, so wrapping
+/// such fields in a `SharedEnv` makes cloning them as cheap as an `Arc::clone`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::block::SharedEnv;
+/// #
+/// #[derive(Clone, Debug)]
+/// struct Environment {
+///     corpus: SharedEnv<Vec<String>>,
+/// }
+///
+/// # pub fn main() {
+/// let environment = Environment {
+///     corpus: SharedEnv::new(vec!["lorem".to_owned(), "ipsum".to_owned()]),
+/// };
+///
+/// rspec::run(&rspec::suite("a corpus", environment, |ctx| {
+///     ctx.it("has two words", |env| env.corpus.len() == 2);
+/// }));
+/// # }
+/// ```
+pub struct SharedEnv<T> {
+    value: Arc<T>,
+}
+
+impl<T> SharedEnv<T> {
+    pub fn new(value: T) -> Self {
+        SharedEnv {
+            value: Arc::new(value),
+        }
+    }
+
+    /// Whether both point to the very same value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.value, &other.value)
+    }
+}
+
+impl<T> Clone for SharedEnv<T> {
+    fn clone(&self) -> Self {
+        SharedEnv {
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl<T> Deref for SharedEnv<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> From<T> for SharedEnv<T> {
+    fn from(value: T) -> Self {
+        SharedEnv::new(value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedEnv<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use block::suite;
+    use runner::Runner;
+
+    /// A value panicking when being deep-cloned.
+    #[derive(Debug)]
+    struct Expensive;
+
+    impl Clone for Expensive {
+        fn clone(&self) -> Self {
+            panic!("expensive value got cloned");
+        }
+    }
+
+    #[test]
+    fn it_shares_the_value_across_examples() {
+        // arrange
+        let runner = Runner::default();
+        let shared = SharedEnv::new(Expensive);
+        let seen: Arc<Mutex<Vec<usize>>> = Arc::new(Mutex::new(vec![]));
+        let suite = {
+            let seen = seen.clone();
+            suite("a suite", shared.clone(), move |ctx| {
+                for _ in 0..3 {
+                    let seen = seen.clone();
+                    ctx.it("sees the shared value", move |env| {
+                        let address = &**env as *const Expensive as usize;
+                        seen.lock().unwrap().push(address);
+                    });
+                }
+            })
+        };
+        // act
+        let report = runner.try_run(&suite);
+        // assert
+        assert!(report.is_ok());
+        let expected = &*shared as *const Expensive as usize;
+        let seen = seen.lock().unwrap();
+        assert_eq!(3, seen.len());
+        assert!(seen.iter().all(|address| *address == expected));
+    }
+
+    #[test]
+    fn ptr_eq() {
+        // arrange
+        let shared = SharedEnv::new(42);
+        // act
+        let cloned = shared.clone();
+        // assert
+        assert!(SharedEnv::ptr_eq(&shared, &cloned));
+        assert!(!SharedEnv::ptr_eq(&shared, &SharedEnv::new(42)));
+    }
+}
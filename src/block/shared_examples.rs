@@ -0,0 +1,79 @@
+//! Named groups of examples shared between contexts,
+//! see [`Context::shared_examples`](../struct.Context.html#method.shared_examples).
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use block::Context;
+
+type SharedGroup<'a, T> = Arc<dyn Fn(&mut Context<'a, T>) + Send + Sync + 'a>;
+
+/// Registry of named groups of examples (and hooks), to be spliced into contexts by name.
+pub struct SharedExamples<'a, T> {
+    // Shared (copy-on-write) by all sub-contexts inheriting the registry:
+    groups: Arc<HashMap<Cow<'static, str>, SharedGroup<'a, T>>>,
+}
+
+impl<'a, T> SharedExamples<'a, T> {
+    pub fn new() -> Self {
+        SharedExamples {
+            groups: Arc::new(HashMap::new()),
+        }
+    }
+
+    /// Registers a group under the given name, replacing any previous group of that name.
+    pub fn register<N, F>(&mut self, name: N, body: F)
+    where
+        N: Into<Cow<'static, str>>,
+        F: 'a + Fn(&mut Context<'a, T>) + Send + Sync,
+    {
+        Arc::make_mut(&mut self.groups).insert(name.into(), Arc::new(body));
+    }
+
+    /// Registers all groups of `other`, replacing any previous groups of the same names.
+    pub(crate) fn extend(&mut self, other: &SharedExamples<'a, T>) {
+        let groups = other.groups.iter();
+        let groups = groups.map(|(name, group)| (name.clone(), group.clone()));
+        Arc::make_mut(&mut self.groups).extend(groups)
+    }
+
+    /// The group registered under the given name, if any.
+    pub(crate) fn get(&self, name: &str) -> Option<SharedGroup<'a, T>> {
+        self.groups.get(name).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+impl<'a, T> Clone for SharedExamples<'a, T> {
+    fn clone(&self) -> Self {
+        SharedExamples {
+            groups: self.groups.clone(),
+        }
+    }
+}
+
+impl<'a, T> Default for SharedExamples<'a, T> {
+    fn default() -> Self {
+        SharedExamples::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register() {
+        // arrange
+        let mut shared = SharedExamples::<()>::new();
+        // act
+        shared.register("a group", |ctx| ctx.example("an example", |_| {}));
+        // assert
+        assert!(shared.get("a group").is_some());
+        assert!(shared.get("another group").is_none());
+    }
+}
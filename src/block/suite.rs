@@ -1,15 +1,19 @@
-use block::Context;
-use header::{SuiteHeader, SuiteLabel};
+use std::borrow::Cow;
+use std::slice;
+
+use block::{Block, Context, MappedContext};
+use header::{ContextHeader, ContextLabel, SuiteHeader, SuiteLabel};
+use runner::Configuration;
 
 /// Test suites bundle a set of closely related test examples into a logical execution group.
 #[derive(new)]
-pub struct Suite<T> {
+pub struct Suite<'a, T> {
     pub(crate) header: SuiteHeader,
     pub(crate) environment: T,
-    pub(crate) context: Context<T>,
+    pub(crate) context: Context<'a, T>,
 }
 
-impl<T> Suite<T> {
+impl<'a, T> Suite<'a, T> {
     pub fn num_blocks(&self) -> usize {
         self.context.num_blocks()
     }
@@ -18,13 +22,111 @@ impl<T> Suite<T> {
         self.context.num_examples()
     }
 
+    /// The number of examples a runner with the given configuration would actually run,
+    /// rather than report as ignored up front (e.g. for not passing its `filter`).
+    ///
+    /// Examples ignored only at run time (e.g. by [`Context::skip_if`](struct.Context.html#method.skip_if))
+    /// are counted as selected.
+    pub fn num_selected(&self, configuration: &Configuration) -> usize {
+        if configuration.dry_run {
+            return 0;
+        }
+        match configuration.filter {
+            // Pruning uses the very same predicate on examples as the runner's filtering:
+            Some(ref filter) => self.context.filtered(&**filter).num_examples(),
+            None => self.num_examples(),
+        }
+    }
+
+    /// The number of contexts (including name-less scopes) within the suite.
+    pub fn num_contexts(&self) -> usize {
+        self.context.num_contexts()
+    }
+
+    /// The maximum number of levels of contexts nested within the suite.
+    pub fn max_depth(&self) -> usize {
+        self.context.max_depth()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.context.is_empty()
     }
+
+    /// A copy of the suite, containing only the examples whose path (i.e. the names of
+    /// their enclosing contexts, followed by their own name) passes `predicate`.
+    ///
+    /// Contexts left without any examples get dropped. The copy shares the original suite's
+    /// closures (examples, hooks, …), while getting its own clone of the environment.
+    pub fn filtered<P>(&self, predicate: P) -> Suite<'a, T>
+    where
+        P: Fn(&[&str]) -> bool,
+        T: Clone,
+    {
+        Suite {
+            header: self.header.clone(),
+            environment: self.environment.clone(),
+            context: self.context.filtered(&predicate),
+        }
+    }
 }
 
-unsafe impl<T> Send for Suite<T> where T: Send {}
-unsafe impl<T> Sync for Suite<T> where T: Sync {}
+impl<'a, T> Suite<'a, T>
+where
+    T: 'a + Clone + Send + Sync + ::std::fmt::Debug,
+{
+    /// Combines independently built suites (e.g. one per module) into a single suite,
+    /// nesting each of their root contexts within a context named after the suite.
+    ///
+    /// Each of the nested suites keeps getting evaluated with its own environment,
+    /// the merged suite's environment being that of the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no suites are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # use rspec::block::Suite;
+    /// #
+    /// # pub fn main() {
+    /// let parser = rspec::describe("the parser", (), |ctx| {
+    ///     ctx.it("parses", |_env| { /* … */ });
+    /// });
+    /// let printer = rspec::describe("the printer", (), |ctx| {
+    ///     ctx.it("prints", |_env| { /* … */ });
+    /// });
+    /// rspec::run(&Suite::merge("all modules", vec![parser, printer]));
+    /// # }
+    /// ```
+    pub fn merge<N>(name: N, suites: Vec<Suite<'a, T>>) -> Suite<'a, T>
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        let environment = match suites.first() {
+            Some(suite) => suite.environment.clone(),
+            None => panic!("no suites to merge"),
+        };
+        let mut root = Context::new(None);
+        for suite in suites {
+            let name = suite.header.name;
+            let mut context = suite.context;
+            context.prefix_paths(slice::from_ref(&name));
+            context.header = Some(ContextHeader::new(ContextLabel::Context, name));
+            let suite_environment = suite.environment;
+            let map = move |_: &T| suite_environment.clone();
+            root.blocks
+                .push(Block::Mapped(MappedContext::new(map, context)));
+        }
+        let header = SuiteHeader {
+            label: SuiteLabel::Suite,
+            name: name.into(),
+        };
+        Suite::new(header, environment, root)
+    }
+}
 
 /// Creates a test suite from a given root context.
 ///
@@ -59,14 +161,15 @@ unsafe impl<T> Sync for Suite<T> where T: Sync {}
 ///
 /// - [`describe`](fn.describe.html).
 /// - [`given`](fn.given.html).
-pub fn suite<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
+pub fn suite<'a, N, F, T>(name: N, environment: T, body: F) -> Suite<'a, T>
 where
-    F: FnOnce(&mut Context<T>),
+    N: Into<Cow<'static, str>>,
+    F: FnOnce(&mut Context<'a, T>),
     T: Clone + ::std::fmt::Debug,
 {
     let header = SuiteHeader {
         label: SuiteLabel::Suite,
-        name,
+        name: name.into(),
     };
     suite_internal(header, environment, body)
 }
@@ -76,14 +179,15 @@ where
 /// Available further aliases:
 ///
 /// - [`given`](fn.describe.html).
-pub fn describe<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
+pub fn describe<'a, N, F, T>(name: N, environment: T, body: F) -> Suite<'a, T>
 where
-    F: FnOnce(&mut Context<T>),
+    N: Into<Cow<'static, str>>,
+    F: FnOnce(&mut Context<'a, T>),
     T: Clone + ::std::fmt::Debug,
 {
     let header = SuiteHeader {
         label: SuiteLabel::Describe,
-        name,
+        name: name.into(),
     };
     suite_internal(header, environment, body)
 }
@@ -93,21 +197,22 @@ where
 /// Available further aliases:
 ///
 /// - [`describe`](fn.describe.html).
-pub fn given<F, T>(name: &'static str, environment: T, body: F) -> Suite<T>
+pub fn given<'a, N, F, T>(name: N, environment: T, body: F) -> Suite<'a, T>
 where
-    F: FnOnce(&mut Context<T>),
+    N: Into<Cow<'static, str>>,
+    F: FnOnce(&mut Context<'a, T>),
     T: Clone + ::std::fmt::Debug,
 {
     let header = SuiteHeader {
         label: SuiteLabel::Given,
-        name,
+        name: name.into(),
     };
     suite_internal(header, environment, body)
 }
 
-fn suite_internal<F, T>(header: SuiteHeader, environment: T, body: F) -> Suite<T>
+fn suite_internal<'a, F, T>(header: SuiteHeader, environment: T, body: F) -> Suite<'a, T>
 where
-    F: FnOnce(&mut Context<T>),
+    F: FnOnce(&mut Context<'a, T>),
     T: Clone + ::std::fmt::Debug,
 {
     let mut ctx = Context::new(None);
@@ -119,13 +224,165 @@ where
 mod tests {
     use super::*;
 
+    use block::Lets;
+    use report::{BlockReport, Report};
+    use runner::{ConfigurationBuilder, Runner};
+    use std::sync::Arc;
+
+    #[test]
+    fn filtered() {
+        // arrange
+        let suite = suite("name", (), |ctx| {
+            ctx.example("first", |_| {});
+            ctx.context("context", |ctx| {
+                ctx.example("second", |_| {});
+                ctx.context("empty", |ctx| {
+                    ctx.example("third", |_| {});
+                });
+            });
+        });
+        // act
+        let filtered = suite.filtered(|path| path == ["context", "second"]);
+        // assert
+        assert_eq!(1, filtered.num_examples());
+        assert_eq!(1, filtered.num_contexts());
+        assert_eq!(3, suite.num_examples());
+    }
+
+    #[test]
+    fn num_selected() {
+        // arrange
+        let suite = suite("name", (), |ctx| {
+            ctx.example("first", |_| {});
+            ctx.context("context", |ctx| {
+                ctx.example("second", |_| {});
+                ctx.example("third", |_| {});
+            });
+        });
+        let filter = |path: &[&str]| path == ["context", "second"];
+        let configuration = ConfigurationBuilder::default()
+            .filter(Arc::new(filter))
+            .build()
+            .unwrap();
+        // act
+        let selected = suite.num_selected(&configuration);
+        // assert
+        assert_eq!(1, selected);
+        assert_eq!(3, suite.num_examples());
+        assert_eq!(3, suite.num_selected(&Configuration::default()));
+        let report = Runner::new(configuration, vec![]).run(&suite);
+        assert_eq!(selected as u32, report.get_passed());
+    }
+
+    #[test]
+    fn num_selected_in_dry_runs() {
+        // arrange
+        let suite = suite("name", (), |ctx| ctx.example("example", |_| {}));
+        let configuration = ConfigurationBuilder::default()
+            .dry_run(true)
+            .build()
+            .unwrap();
+        // act
+        let selected = suite.num_selected(&configuration);
+        // assert
+        assert_eq!(0, selected);
+    }
+
+    #[test]
+    fn merge() {
+        // arrange
+        let first = suite("first", 1, |ctx| {
+            ctx.example("one", |env| *env == 1);
+            ctx.context("a context", |ctx| {
+                ctx.example("two", |env| *env == 1);
+            });
+        });
+        let second = describe("second", 2, |ctx| {
+            ctx.example("three", |env| *env == 2);
+        });
+        // act
+        let merged = Suite::merge("merged", vec![first, second]);
+        // assert
+        assert_eq!(3, merged.num_examples());
+        assert_eq!(2, merged.num_blocks());
+        assert!(merged
+            .context
+            .contains_example(&["first", "a context", "two"]));
+        assert!(merged.context.contains_example(&["second", "three"]));
+        let filter = |path: &[&str]| path == ["first", "a context", "two"];
+        let configuration = ConfigurationBuilder::default()
+            .filter(Arc::new(filter))
+            .build()
+            .unwrap();
+        assert_eq!(1, merged.num_selected(&configuration));
+        let report = Runner::new(Configuration::default(), vec![]).run(&merged);
+        assert_eq!(3, report.get_passed());
+        let names: Vec<_> = report
+            .get_context()
+            .get_blocks()
+            .iter()
+            .map(|block| match block {
+                BlockReport::Context(Some(ref header), _) => header.to_string(),
+                _ => panic!("expected a named context"),
+            })
+            .collect();
+        assert_eq!(vec!["Context \"first\"", "Context \"second\""], names);
+    }
+
+    #[test]
+    fn borrows_from_the_enclosing_scope() {
+        #[derive(Clone, Debug, Default)]
+        struct Environment {
+            lets: Lets,
+        }
+
+        impl AsMut<Lets> for Environment {
+            fn as_mut(&mut self) -> &mut Lets {
+                &mut self.lets
+            }
+        }
+
+        // arrange
+        let values: Vec<i32> = vec![1, 2, 3];
+        let first = suite("first", Environment::default(), |ctx| {
+            ctx.example("borrows", |_| values.len() == 3);
+            ctx.context("context", |ctx| {
+                ctx.example("borrows too", |_| values.contains(&2));
+            });
+        });
+        let second = suite("second", Environment::default(), |ctx| {
+            ctx.subject(|| 2);
+            ctx.let_value("three", || 3);
+            ctx.example("borrows alongside lets", |env| {
+                values.contains(&*env.lets.subject::<i32>())
+                    && values.contains(&*env.lets.get::<i32>("three"))
+            });
+        });
+        let suite = Suite::merge("merged", vec![first, second]);
+        // act
+        let report = Runner::new(Configuration::default(), vec![]).run(&suite);
+        // assert
+        assert_eq!(3, report.get_passed());
+        assert_eq!(vec![1, 2, 3], values);
+    }
+
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_sync<S: Send + Sync>(_: &S) {}
+        let suite = suite("name", (), |ctx| {
+            ctx.before_each(|_| {});
+            ctx.example("example", |_| {});
+        });
+        assert_send_sync(&suite);
+    }
+
     #[test]
     fn empty_suite() {
         let suite = suite("name", (), |_| {});
         assert_eq!(suite.header.label, SuiteLabel::Suite);
         assert_eq!(suite.header.name, "name");
         assert_eq!(suite.environment, ());
-        assert_eq!(suite.is_empty(), true);
+        assert!(suite.is_empty());
         assert_eq!(suite.num_examples(), 0);
     }
 
@@ -135,7 +392,7 @@ mod tests {
         assert_eq!(describe.header.label, SuiteLabel::Describe);
         assert_eq!(describe.header.name, "name");
         assert_eq!(describe.environment, ());
-        assert_eq!(describe.is_empty(), true);
+        assert!(describe.is_empty());
         assert_eq!(describe.num_examples(), 0);
     }
 
@@ -145,7 +402,7 @@ mod tests {
         assert_eq!(given.header.label, SuiteLabel::Given);
         assert_eq!(given.header.name, "name");
         assert_eq!(given.environment, ());
-        assert_eq!(given.is_empty(), true);
+        assert!(given.is_empty());
         assert_eq!(given.num_examples(), 0);
     }
 
@@ -157,7 +414,35 @@ mod tests {
         assert_eq!(suite.header.label, SuiteLabel::Suite);
         assert_eq!(suite.header.name, "suite");
         assert_eq!(suite.environment, ());
-        assert_eq!(suite.is_empty(), false);
+        assert!(!suite.is_empty());
         assert_eq!(suite.num_examples(), 0);
     }
+
+    #[test]
+    fn nested_suite() {
+        let suite = suite("suite", (), |ctx| {
+            ctx.example("example", |_| {});
+            ctx.context("context", |ctx| {
+                ctx.context("nested context", |ctx| {
+                    ctx.scope(|ctx| {
+                        ctx.example("example", |_| {});
+                    });
+                });
+                ctx.example("example", |_| {});
+            });
+            ctx.context("another context", |_| {});
+        });
+        assert_eq!(suite.num_examples(), 3);
+        assert_eq!(suite.num_contexts(), 4);
+        assert_eq!(suite.max_depth(), 3);
+    }
+
+    #[test]
+    fn flat_suite() {
+        let suite = suite("suite", (), |ctx| {
+            ctx.example("example", |_| {});
+        });
+        assert_eq!(suite.num_contexts(), 0);
+        assert_eq!(suite.max_depth(), 0);
+    }
 }
@@ -1,10 +1,12 @@
+use std::borrow::Cow;
 use std::fmt;
 
 /// How the [`Context`](../block/struct.Context.html) will be printed by the [`Logger`](../logger/index.html).
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ContextLabel {
     Context,
     Specify,
+    Given,
     When,
 }
 
@@ -13,21 +15,46 @@ impl fmt::Display for ContextLabel {
         match self {
             ContextLabel::Context => write!(f, "Context"),
             ContextLabel::Specify => write!(f, "Specify"),
+            ContextLabel::Given => write!(f, "Given"),
             ContextLabel::When => write!(f, "When"),
         }
     }
 }
 
 /// A [`Header`](trait.Header.html) with label and name of a [`Context`](../block/struct.Context.html).
-#[derive(Clone, PartialEq, Eq, Debug, new)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ContextHeader {
     pub label: ContextLabel,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
+    /// Rendered in place of the label (e.g. "Scenario" rather than "Context"), if any.
+    pub display_label_override: Option<&'static str>,
+}
+
+impl ContextHeader {
+    pub fn new<N>(label: ContextLabel, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        ContextHeader {
+            label,
+            name: name.into(),
+            display_label_override: None,
+        }
+    }
+
+    /// Renders the header with the given label in place of its own.
+    pub fn with_display_label(mut self, label: &'static str) -> Self {
+        self.display_label_override = Some(label);
+        self
+    }
 }
 
 impl fmt::Display for ContextHeader {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {:?}", self.label, self.name)
+        match self.display_label_override {
+            Some(label) => write!(f, "{} {:?}", label, self.name),
+            None => write!(f, "{} {:?}", self.label, self.name),
+        }
     }
 }
 
@@ -35,13 +62,16 @@ impl fmt::Display for ContextHeader {
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
+
     #[test]
     fn label_fmt() {
         fn subject(label: ContextLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(ContextLabel::Context), "Context".to_owned());
         assert_eq!(subject(ContextLabel::Specify), "Specify".to_owned());
+        assert_eq!(subject(ContextLabel::Given), "Given".to_owned());
         assert_eq!(subject(ContextLabel::When), "When".to_owned());
     }
 
@@ -49,7 +79,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: ContextLabel) -> String {
             format!("{}", ContextHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(
             subject(ContextLabel::Context),
             "Context \"Test\"".to_owned()
@@ -58,6 +88,25 @@ mod tests {
             subject(ContextLabel::Specify),
             "Specify \"Test\"".to_owned()
         );
+        assert_eq!(subject(ContextLabel::Given), "Given \"Test\"".to_owned());
         assert_eq!(subject(ContextLabel::When), "When \"Test\"".to_owned());
     }
+
+    #[test]
+    fn header_fmt_with_display_label() {
+        let header =
+            ContextHeader::new(ContextLabel::Context, "Test").with_display_label("Scenario");
+        assert_eq!("Scenario \"Test\"", header.to_string());
+    }
+
+    #[test]
+    fn header_hash() {
+        // arrange
+        let mut headers = HashSet::new();
+        // act
+        headers.insert(ContextHeader::new(ContextLabel::Context, "Test"));
+        // assert
+        assert!(headers.contains(&ContextHeader::new(ContextLabel::Context, "Test")));
+        assert!(!headers.contains(&ContextHeader::new(ContextLabel::Context, "Other")));
+    }
 }
@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::panic::Location;
 
 /// How the [`Example`](../block/struct.Example.html) will be printed by the [`Logger`](../logger/index.html).
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ExampleLabel {
     It,
     Example,
@@ -19,10 +21,34 @@ impl fmt::Display for ExampleLabel {
 }
 
 /// A [`Header`](trait.Header.html) with label and name of an [`Example`](../block/struct.Example.html).
-#[derive(Clone, PartialEq, Eq, Debug, new)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ExampleHeader {
     pub label: ExampleLabel,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
+    location: Option<&'static Location<'static>>,
+}
+
+impl ExampleHeader {
+    pub fn new<N>(label: ExampleLabel, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        ExampleHeader {
+            label,
+            name: name.into(),
+            location: None,
+        }
+    }
+
+    pub(crate) fn with_location(mut self, location: &'static Location<'static>) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// The source location the example got declared at (if known).
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
 }
 
 #[cfg(test)]
@@ -43,11 +69,13 @@ impl fmt::Display for ExampleHeader {
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
+
     #[test]
     fn label_fmt() {
         fn subject(label: ExampleLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(ExampleLabel::Example), "Example".to_owned());
         assert_eq!(subject(ExampleLabel::It), "It".to_owned());
         assert_eq!(subject(ExampleLabel::Then), "Then".to_owned());
@@ -57,7 +85,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: ExampleLabel) -> String {
             format!("{}", ExampleHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(
             subject(ExampleLabel::Example),
             "Example \"Test\"".to_owned()
@@ -65,4 +93,25 @@ mod tests {
         assert_eq!(subject(ExampleLabel::It), "It \"Test\"".to_owned());
         assert_eq!(subject(ExampleLabel::Then), "Then \"Test\"".to_owned());
     }
+
+    #[test]
+    fn header_fmt_with_computed_name() {
+        // arrange
+        let (lhs, rhs) = (2, 3);
+        // act
+        let header = ExampleHeader::new(ExampleLabel::It, format!("adds {} and {} ≠ 6", lhs, rhs));
+        // assert
+        assert_eq!("It \"adds 2 and 3 ≠ 6\"", header.to_string());
+    }
+
+    #[test]
+    fn header_hash() {
+        // arrange
+        let mut headers = HashSet::new();
+        // act
+        headers.insert(ExampleHeader::new(ExampleLabel::It, "Test"));
+        // assert
+        assert!(headers.contains(&ExampleHeader::new(ExampleLabel::It, "Test")));
+        assert!(!headers.contains(&ExampleHeader::new(ExampleLabel::It, "Other")));
+    }
 }
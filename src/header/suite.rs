@@ -1,7 +1,8 @@
+use std::borrow::Cow;
 use std::fmt;
 
 /// How the [`Suite`](../block/struct.Suite.html) will be printed by the [`Logger`](../logger/index.html).
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum SuiteLabel {
     Suite,
     Describe,
@@ -19,10 +20,22 @@ impl fmt::Display for SuiteLabel {
 }
 
 /// A [`Header`](trait.Header.html) with label and name of a [`Suite`](../block/struct.Suite.html).
-#[derive(Clone, PartialEq, Eq, Debug, new)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct SuiteHeader {
     pub label: SuiteLabel,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
+}
+
+impl SuiteHeader {
+    pub fn new<N>(label: SuiteLabel, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        SuiteHeader {
+            label,
+            name: name.into(),
+        }
+    }
 }
 
 impl fmt::Display for SuiteHeader {
@@ -35,11 +48,13 @@ impl fmt::Display for SuiteHeader {
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
+
     #[test]
     fn label_fmt() {
         fn subject(label: SuiteLabel) -> String {
             format!("{}", label)
-        };
+        }
         assert_eq!(subject(SuiteLabel::Suite), "Suite".to_owned());
         assert_eq!(subject(SuiteLabel::Describe), "Describe".to_owned());
         assert_eq!(subject(SuiteLabel::Given), "Given".to_owned());
@@ -49,7 +64,7 @@ mod tests {
     fn header_fmt() {
         fn subject(label: SuiteLabel) -> String {
             format!("{}", SuiteHeader::new(label, "Test"))
-        };
+        }
         assert_eq!(subject(SuiteLabel::Suite), "Suite \"Test\"".to_owned());
         assert_eq!(
             subject(SuiteLabel::Describe),
@@ -57,4 +72,15 @@ mod tests {
         );
         assert_eq!(subject(SuiteLabel::Given), "Given \"Test\"".to_owned());
     }
+
+    #[test]
+    fn header_hash() {
+        // arrange
+        let mut headers = HashSet::new();
+        // act
+        headers.insert(SuiteHeader::new(SuiteLabel::Suite, "Test"));
+        // assert
+        assert!(headers.contains(&SuiteHeader::new(SuiteLabel::Suite, "Test")));
+        assert!(!headers.contains(&SuiteHeader::new(SuiteLabel::Suite, "Other")));
+    }
 }
@@ -9,11 +9,14 @@ extern crate derive_builder;
 #[macro_use]
 extern crate derive_new;
 
+#[cfg(feature = "color")]
 extern crate colored;
 #[cfg(feature = "expectest_compat")]
 extern crate expectest;
+#[cfg(feature = "futures")]
+extern crate futures_executor;
+#[cfg(feature = "parallel")]
 extern crate rayon;
-extern crate time;
 
 pub mod block;
 pub mod header;
@@ -24,10 +27,14 @@ pub mod runner;
 mod visitor;
 
 pub use block::{describe, given, suite};
-pub use logger::Logger;
-pub use runner::{Configuration, ConfigurationBuilder, Runner};
+pub use logger::{
+    ColorChoice, CountingLogger, FailuresOnlyLogger, FileReportLogger, Logger, ProfileLogger, Theme,
+};
+pub use report::{expect_eq, SoftAssertions};
+pub use runner::{ConfigError, Configuration, ConfigurationBuilder, Layout, Runner};
 
 use block::Suite;
+use report::SuiteReport;
 
 /// A wrapper for conveniently running a test suite with
 /// the default configuration with considerebly less glue-code.
@@ -51,22 +58,97 @@ pub fn run<T>(suite: &Suite<T>)
 where
     T: Clone + Send + Sync + ::std::fmt::Debug,
 {
-    use std::io;
+    run_with(suite, |_| {})
+}
+
+/// Like [`run`](fn.run.html), but letting `configure` adjust the configuration
+/// before running the test suite (still logging to stdout).
+///
+/// # Panics
+///
+/// If the adjusted configuration is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # pub fn main() {
+/// rspec::run_with(
+///     &rspec::suite("a suite", (), |ctx| {
+///         ctx.it("passes", |_env| true);
+///     }),
+///     |config| {
+///         config.parallel(false);
+///     },
+/// );
+/// # }
+/// ```
+pub fn run_with<T, F>(suite: &Suite<T>, configure: F)
+where
+    T: Clone + Send + Sync + ::std::fmt::Debug,
+    F: FnOnce(&mut ConfigurationBuilder),
+{
+    let runner = logging_runner(::std::io::stdout(), configure);
+
+    let _ = runner.run(suite);
+}
+
+/// The runner of [`run_with`](fn.run_with.html), logging to the given buffer.
+fn logging_runner<W, F>(buffer: W, configure: F) -> Runner
+where
+    W: 'static + ::std::io::Write + Send + Sync,
+    F: FnOnce(&mut ConfigurationBuilder),
+{
     use std::sync::Arc;
 
-    let logger = Arc::new(Logger::new(io::stdout()));
-    let configuration = ConfigurationBuilder::default().build().unwrap();
-    let runner = Runner::new(configuration, vec![logger]);
+    let logger = Arc::new(Logger::new(buffer));
+    let mut builder = ConfigurationBuilder::default();
+    configure(&mut builder);
+    let configuration = builder.build().unwrap();
+    Runner::new(configuration, vec![logger])
+}
 
-    runner.run(suite);
+/// Evaluates a test suite silently, returning its report.
+///
+/// Unlike [`run`](fn.run.html) this attaches no observers (and thus writes no output),
+/// swallows the output of panicking examples and never exits the process on failure,
+/// for asserting on the report programmatically.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::report::Report;
+/// #
+/// # pub fn main() {
+/// let report = rspec::evaluate(&rspec::suite("a suite", (), |ctx| {
+///     ctx.it("passes", |_env| true);
+///     ctx.it("fails", |_env| false);
+/// }));
+///
+/// assert_eq!(1, report.get_failed());
+/// # }
+/// ```
+pub fn evaluate<T>(suite: &Suite<T>) -> SuiteReport
+where
+    T: Clone + Send + Sync + ::std::fmt::Debug,
+{
+    let configuration = ConfigurationBuilder::default()
+        .exit_on_failure(false)
+        .swallow_panics(true)
+        .build()
+        .unwrap();
+    let runner = Runner::new(configuration, vec![]);
+
+    match runner.try_run(suite) {
+        Ok(report) | Err(report) => report,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-
-    pub use super::*;
-    pub use block::*;
-
     // Test list:
     // x check that tests can call `assert_eq!`
     // x check that tests can return Err or Ok
@@ -93,4 +175,53 @@ mod tests {
     // - use Any to return anything that can be Ok-ed or () or None or panic-ed
     // - bench ? --> see what's the protocol
     //
+
+    use super::*;
+
+    use report::Report;
+    use std::io;
+    use std::sync::Mutex;
+    use std::thread;
+
+    #[test]
+    fn run_with() {
+        // arrange
+        let threads = Mutex::new(vec![]);
+        let suite = suite("a suite", (), |ctx| {
+            ctx.it("runs", |_| {
+                threads.lock().unwrap().push(thread::current().id())
+            });
+            ctx.it("runs too", |_| {
+                threads.lock().unwrap().push(thread::current().id())
+            });
+        });
+        let runner = logging_runner(io::sink(), |config| {
+            config.parallel(false);
+        });
+        // act
+        let report = runner.run(&suite);
+        // assert
+        assert_eq!(2, report.get_passed());
+        let current = thread::current().id();
+        assert_eq!(vec![current, current], *threads.lock().unwrap());
+    }
+
+    #[test]
+    fn evaluate() {
+        // arrange
+        let suite = suite("a suite", (), |ctx| {
+            ctx.it("passes", |_| true);
+            ctx.it("panics", |_| -> bool { panic!("failure") });
+        });
+        // act
+        let report = super::evaluate(&suite);
+        // assert
+        assert_eq!((1, 1), (report.get_passed(), report.get_failed()));
+        // The panic's output got kept for the report, rather than printed:
+        let output = report
+            .get_context()
+            .find_example(&["panics"])
+            .and_then(|example| example.get_captured_output());
+        assert!(output.is_some_and(|output| output.contains("failure")));
+    }
 }
@@ -0,0 +1,179 @@
+use std::io;
+use std::sync::Mutex;
+
+use header::{ExampleHeader, SuiteHeader};
+use logger::theme::Theme;
+use report::{ExampleReport, ExampleResult, Report};
+use runner::{Runner, RunnerObserver};
+
+struct CountingLoggerState<T: io::Write> {
+    buffer: T,
+    completed: usize,
+}
+
+/// Logger writing a line per completed example, prefixed with a running `[n/total]` counter,
+/// for logs that are not being written to a terminal (e.g. on CI).
+///
+/// The counter starts over with each suite.
+pub struct CountingLogger<T: io::Write = io::Stdout> {
+    state: Mutex<CountingLoggerState<T>>,
+    error: Mutex<Option<io::Error>>,
+    total: usize,
+    theme: Theme,
+}
+
+impl<T: io::Write> CountingLogger<T> {
+    /// Creates a logger counting up to `total`,
+    /// usually the [`num_examples`](../block/struct.Suite.html#method.num_examples) of the suite.
+    pub fn new(buffer: T, total: usize) -> Self {
+        let state = CountingLoggerState {
+            buffer,
+            completed: 0,
+        };
+        CountingLogger {
+            state: Mutex::new(state),
+            error: Mutex::new(None),
+            total,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Words the flags after the given theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn write_example<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        completed: usize,
+        header: &ExampleHeader,
+        report: &ExampleReport,
+    ) -> io::Result<()> {
+        let width = self.total.to_string().len();
+        let flag = if report.is_failure() {
+            &self.theme.failure
+        } else if let ExampleResult::Ignored(_) = *report.get_result() {
+            &self.theme.ignored
+        } else {
+            &self.theme.success
+        };
+        writeln!(
+            buffer,
+            "[{:>width$}/{}] {} ... {}",
+            completed,
+            self.total,
+            header,
+            flag,
+            width = width
+        )?;
+        buffer.flush()
+    }
+
+    fn record_error(&self, error: io::Error) {
+        let mut first_error = self
+            .error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if first_error.is_none() {
+            *first_error = Some(error);
+        }
+    }
+}
+
+impl<T: io::Write> RunnerObserver for CountingLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_suite(&self, _runner: &Runner, _header: &SuiteHeader) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.completed = 0;
+    }
+
+    fn exit_example(&self, _runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.completed += 1;
+        let state = &mut *state;
+        if let Err(error) = self.write_example(&mut state.buffer, state.completed, header, report) {
+            self.record_error(error);
+        }
+    }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use runner::{Configuration, ConfigurationBuilder};
+    use std::sync::Arc;
+
+    fn output(logger: &CountingLogger<Vec<u8>>) -> String {
+        let state = logger.state.lock().unwrap();
+        String::from_utf8(state.buffer.clone()).unwrap()
+    }
+
+    fn serial_configuration() -> Configuration {
+        ConfigurationBuilder::default()
+            .parallel(false)
+            .exit_on_failure(false)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_counts_up_to_the_total() {
+        // arrange
+        let suite = suite("a suite", (), |ctx| {
+            for i in 0..10 {
+                ctx.example(format!("example {}", i), move |_| i != 9);
+            }
+        });
+        let logger = Arc::new(CountingLogger::new(vec![], suite.num_examples()));
+        let runner = Runner::new(serial_configuration(), vec![logger.clone()]);
+        // act
+        let _ = runner.run(&suite);
+        // assert
+        let output = output(&logger);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(10, lines.len());
+        assert_eq!("[ 1/10] Example \"example 0\" ... ok", lines[0]);
+        assert_eq!("[ 9/10] Example \"example 8\" ... ok", lines[8]);
+        assert_eq!("[10/10] Example \"example 9\" ... FAILED", lines[9]);
+    }
+
+    #[test]
+    fn it_starts_over_with_each_suite() {
+        // arrange
+        let suite = suite("a suite", (), |ctx| {
+            ctx.example("first", |_| true);
+            ctx.example("second", |_| true);
+        });
+        let logger = Arc::new(CountingLogger::new(vec![], suite.num_examples()));
+        let runner = Runner::new(serial_configuration(), vec![logger.clone()]);
+        // act
+        let _ = runner.run(&suite);
+        let _ = runner.run(&suite);
+        // assert
+        let expected = "[1/2] Example \"first\" ... ok
+[2/2] Example \"second\" ... ok
+[1/2] Example \"first\" ... ok
+[2/2] Example \"second\" ... ok
+";
+        assert_eq!(expected, output(&logger));
+    }
+}
@@ -0,0 +1,76 @@
+//! Line-based diffing of expected and actual values.
+
+/// A single line of a diff between an expected and an actual value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum DiffLine<'a> {
+    /// A line present in both values.
+    Same(&'a str),
+    /// A line only present in the expected value.
+    Removed(&'a str),
+    /// A line only present in the actual value.
+    Added(&'a str),
+}
+
+/// Computes a line-based diff based on the longest common subsequence of both values' lines.
+pub(crate) fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<DiffLine<'a>> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lengths[i][j] holds the length of the LCS of `expected[i..]` and `actual[j..]`:
+    let mut lengths = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            lines.push(DiffLine::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Removed(expected[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(actual[j]));
+            j += 1;
+        }
+    }
+    lines.extend(expected[i..].iter().map(|line| DiffLine::Removed(line)));
+    lines.extend(actual[j..].iter().map(|line| DiffLine::Added(line)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_diffs_identical_values() {
+        assert_eq!(
+            vec![DiffLine::Same("a"), DiffLine::Same("b")],
+            diff_lines("a\nb", "a\nb")
+        );
+    }
+
+    #[test]
+    fn it_diffs_changed_lines() {
+        assert_eq!(
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ],
+            diff_lines("a\nb\nc", "a\nx\nc\nd")
+        );
+    }
+}
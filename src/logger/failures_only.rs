@@ -0,0 +1,317 @@
+use std::io;
+use std::sync::Mutex;
+
+use header::{ContextHeader, ExampleHeader, SuiteHeader};
+use logger::serial::SerialLogger;
+use logger::style::{ColorChoice, Colorize};
+use logger::theme::Theme;
+use report::{BlockReport, ContextReport, ExampleReport, Report, SuiteReport};
+use runner::{Runner, RunnerObserver};
+
+struct FailuresOnlyLoggerState<T: io::Write> {
+    buffer: T,
+    /// The headers of the contexts currently being evaluated.
+    contexts: Vec<String>,
+}
+
+/// Logger writing nothing but failed examples (as soon as they fail) and the suite's summary,
+/// e.g. for re-running a test suite on every change.
+///
+/// For parallel test suite execution the failures get written once each of the suite's
+/// top-level blocks has been evaluated, as their paths can only be told from its report.
+pub struct FailuresOnlyLogger<T: io::Write = io::Stdout> {
+    state: Mutex<FailuresOnlyLoggerState<T>>,
+    error: Mutex<Option<io::Error>>,
+    // Only used for formatting failures and the summary, its own buffer remains unused:
+    format: SerialLogger<io::Sink>,
+    theme: Theme,
+}
+
+impl Default for FailuresOnlyLogger<io::Stdout> {
+    fn default() -> Self {
+        FailuresOnlyLogger::new(io::stdout())
+    }
+}
+
+impl<T: io::Write> FailuresOnlyLogger<T> {
+    pub fn new(buffer: T) -> Self {
+        let state = FailuresOnlyLoggerState {
+            buffer,
+            contexts: vec![],
+        };
+        FailuresOnlyLogger {
+            state: Mutex::new(state),
+            error: Mutex::new(None),
+            format: SerialLogger::new(io::sink()),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Words the flags and summary after the given theme.
+    pub fn with_theme(self, theme: Theme) -> Self {
+        FailuresOnlyLogger {
+            format: self.format.with_theme(theme.clone()),
+            theme,
+            ..self
+        }
+    }
+
+    /// Styles the output according to the given choice (defaults to `ColorChoice::Auto`).
+    pub fn with_color(self, choice: ColorChoice) -> Self {
+        FailuresOnlyLogger {
+            format: self.format.with_color(choice),
+            ..self
+        }
+    }
+
+    fn access_state<F>(&self, accessor: F)
+    where
+        F: FnOnce(&mut FailuresOnlyLoggerState<T>) -> io::Result<()>,
+    {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(error) = accessor(&mut state) {
+            let mut first_error = self
+                .error
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if first_error.is_none() {
+                *first_error = Some(error);
+            }
+        }
+    }
+
+    fn write_failure<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        contexts: &[String],
+        header: &ExampleHeader,
+        report: &ExampleReport,
+    ) -> io::Result<()> {
+        for context in contexts {
+            write!(buffer, "{} > ", context)?;
+        }
+        write!(
+            buffer,
+            "{} ... {}",
+            header,
+            self.format.paint(self.theme.failure.as_str().red())
+        )?;
+        match header.location() {
+            Some(location) => writeln!(buffer, " ({}:{})", location.file(), location.line())?,
+            None => writeln!(buffer)?,
+        }
+        self.format.write_example_failure(buffer, 1, report)?;
+        buffer.flush()
+    }
+
+    fn write_block_failures<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        contexts: &mut Vec<String>,
+        report: &BlockReport,
+    ) -> io::Result<()> {
+        if !report.is_failure() {
+            return Ok(());
+        }
+        match report {
+            BlockReport::Context(ref header, ref report) => {
+                if let Some(header) = header.as_ref() {
+                    contexts.push(header.to_string());
+                }
+                for block in report.get_blocks() {
+                    self.write_block_failures(buffer, contexts, block)?;
+                }
+                if header.is_some() {
+                    contexts.pop();
+                }
+                Ok(())
+            }
+            BlockReport::Example(ref header, ref report) => {
+                self.write_failure(buffer, contexts, header, report)
+            }
+        }
+    }
+}
+
+impl<T: io::Write> RunnerObserver for FailuresOnlyLogger<T>
+where
+    T: Send + Sync,
+{
+    fn enter_suite(&self, _runner: &Runner, _header: &SuiteHeader) {
+        self.access_state(|state| {
+            state.contexts.clear();
+            Ok(())
+        });
+    }
+
+    fn exit_suite(&self, runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        // Sessions get a single, combined summary of their own:
+        if runner.in_session() {
+            return;
+        }
+        self.access_state(|state| {
+            self.format.write_suite_suffix(&mut state.buffer, report)?;
+            state.buffer.flush()
+        });
+    }
+
+    fn exit_session(&self, _runner: &Runner, report: &SuiteReport) {
+        self.access_state(|state| {
+            self.format.write_suite_suffix(&mut state.buffer, report)?;
+            state.buffer.flush()
+        });
+    }
+
+    fn enter_context(&self, runner: &Runner, header: &ContextHeader) {
+        // Parallel runs' failures get written per top-level block, see `exit_suite_block`:
+        if runner.configuration.parallel {
+            return;
+        }
+        self.access_state(|state| {
+            state.contexts.push(header.to_string());
+            Ok(())
+        });
+    }
+
+    fn exit_context(&self, runner: &Runner, _header: &ContextHeader, _report: &ContextReport) {
+        if runner.configuration.parallel {
+            return;
+        }
+        self.access_state(|state| {
+            state.contexts.pop();
+            Ok(())
+        });
+    }
+
+    fn exit_example(&self, runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {
+        if runner.configuration.parallel || !report.is_failure() {
+            return;
+        }
+        self.access_state(|state| {
+            self.write_failure(&mut state.buffer, &state.contexts, header, report)
+        });
+    }
+
+    fn exit_suite_block(&self, runner: &Runner, _index: usize, report: &BlockReport) {
+        if !runner.configuration.parallel {
+            return;
+        }
+        self.access_state(|state| {
+            self.write_block_failures(&mut state.buffer, &mut vec![], report)
+        });
+    }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use header::{ContextLabel, ExampleLabel, SuiteLabel};
+    use report::ExampleResult;
+    use runner::{Configuration, ConfigurationBuilder};
+    use std::sync::Arc;
+
+    fn output(logger: &FailuresOnlyLogger<Vec<u8>>) -> String {
+        let state = logger.state.lock().unwrap();
+        String::from_utf8(state.buffer.clone()).unwrap()
+    }
+
+    fn serial_runner() -> Runner {
+        let config = ConfigurationBuilder::default()
+            .parallel(false)
+            .build()
+            .unwrap();
+        Runner::new(config, vec![])
+    }
+
+    #[test]
+    fn it_writes_nothing_for_passing_examples() {
+        // arrange
+        let logger = FailuresOnlyLogger::new(vec![]);
+        let runner = serial_runner();
+        let header = ExampleHeader::new(ExampleLabel::It, "passes");
+        let context = ContextHeader::new(ContextLabel::Context, "a context");
+        // act
+        logger.enter_context(&runner, &context);
+        logger.exit_example(&runner, &header, &ExampleReport::default());
+        logger.exit_context(&runner, &context, &ContextReport::empty());
+        // assert
+        assert_eq!("", output(&logger));
+    }
+
+    #[test]
+    fn it_writes_failures_as_soon_as_they_occur() {
+        // arrange
+        let logger = FailuresOnlyLogger::new(vec![]);
+        let runner = serial_runner();
+        let header = ExampleHeader::new(ExampleLabel::It, "fails");
+        let context = ContextHeader::new(ContextLabel::Context, "a context");
+        let report = ExampleReport::from(ExampleResult::failure(Some("boom".to_owned())));
+        // act
+        logger.enter_context(&runner, &context);
+        logger.exit_example(&runner, &header, &report);
+        // assert
+        let expected = "Context \"a context\" > It \"fails\" ... FAILED\n  boom\n";
+        assert_eq!(expected, output(&logger));
+    }
+
+    #[test]
+    fn it_writes_the_paths_of_failures_of_parallel_runs() {
+        // arrange
+        let logger = Arc::new(FailuresOnlyLogger::new(vec![]));
+        let config = ConfigurationBuilder::default()
+            .parallel(true)
+            .exit_on_failure(false)
+            .build()
+            .unwrap();
+        let runner = Runner::new(config, vec![logger.clone()]);
+        let suite = suite("a suite", (), |ctx| {
+            for name in &["first", "second", "third"] {
+                ctx.context(*name, |ctx| {
+                    ctx.example("passes", |_| true);
+                    ctx.context("nested", |ctx| {
+                        ctx.example("fails", |_| false);
+                    });
+                });
+            }
+        });
+        // act
+        let _ = runner.run(&suite);
+        // assert
+        let output = output(&logger);
+        for name in &["first", "second", "third"] {
+            let expected = format!(
+                "Context \"{}\" > Context \"nested\" > Example \"fails\" ... FAILED",
+                name
+            );
+            assert!(output.contains(&expected), "{}", output);
+        }
+        assert!(!output.contains("passes"));
+    }
+
+    #[test]
+    fn it_writes_the_summary() {
+        // arrange
+        let logger = FailuresOnlyLogger::new(vec![]);
+        let runner = Runner::new(Configuration::default(), vec![]);
+        let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+        let report = SuiteReport::empty(header.clone());
+        // act
+        logger.enter_suite(&runner, &header);
+        logger.exit_suite(&runner, &header, &report);
+        // assert
+        let output = output(&logger);
+        assert!(output.contains("test result: ok. 0 passed; 0 failed; 0 ignored; 0 total"));
+    }
+}
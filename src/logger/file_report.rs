@@ -0,0 +1,205 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use header::SuiteHeader;
+use report::{Report, SuiteReport};
+use runner::{Runner, RunnerObserver};
+
+/// Logger persisting the JSON report of each evaluated suite to a file of its own,
+/// e.g. for dashboards following the trend of a test suite over time.
+///
+/// Each report is written to `<directory>/report-<milliseconds since the Unix epoch>.json`
+/// (suffixed with `-1`, `-2`, … should that file already exist, so as to never overwrite
+/// an earlier report), the directory being created if need be, as an object holding the `passed`, `failed`
+/// and `ignored` counts, the `duration_ms` and the [`report`](struct.SuiteReport.html#method.to_tree_json) tree.
+pub struct FileReportLogger {
+    directory: PathBuf,
+    last_path: Mutex<Option<PathBuf>>,
+    error: Mutex<Option<io::Error>>,
+}
+
+impl FileReportLogger {
+    /// Creates a logger writing its reports into `directory`.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        FileReportLogger {
+            directory: directory.into(),
+            last_path: Mutex::new(None),
+            error: Mutex::new(None),
+        }
+    }
+
+    /// The directory reports are written into.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// The path of the last report written, if any.
+    pub fn last_path(&self) -> Option<PathBuf> {
+        self.last_path
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn write_report(&self, report: &SuiteReport) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.directory)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or(0);
+        let (path, mut file) = self.create_report_file(timestamp)?;
+        write!(
+            file,
+            "{{\"passed\":{},\"failed\":{},\"ignored\":{},\"duration_ms\":{},\"report\":{}}}",
+            report.get_passed(),
+            report.get_failed(),
+            report.get_ignored(),
+            report.get_wall_duration().as_millis(),
+            report.to_tree_json()
+        )?;
+        file.flush()?;
+        Ok(path)
+    }
+
+    /// Creates the first file of the report's name not taken yet.
+    fn create_report_file(&self, timestamp: u128) -> io::Result<(PathBuf, fs::File)> {
+        let mut attempt = 0;
+        loop {
+            let name = match attempt {
+                0 => format!("report-{}.json", timestamp),
+                _ => format!("report-{}-{}.json", timestamp, attempt),
+            };
+            let path = self.directory.join(name);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl RunnerObserver for FileReportLogger {
+    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        match self.write_report(report) {
+            Ok(path) => {
+                *self
+                    .last_path
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(path);
+            }
+            Err(error) => {
+                let mut first_error = self
+                    .error
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                if first_error.is_none() {
+                    *first_error = Some(error);
+                }
+            }
+        }
+    }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::process;
+    use std::sync::Arc;
+
+    use block::suite;
+    use runner::ConfigurationBuilder;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("rspec-{}-{}", name, process::id()))
+    }
+
+    #[test]
+    fn it_writes_the_report_into_a_new_directory() {
+        // arrange
+        let directory = temp_dir("file-report").join("nested");
+        let _ = fs::remove_dir_all(&directory);
+        let suite = suite("a suite", (), |ctx| {
+            ctx.example("passing", |_| true);
+            ctx.example("failing", |_| false);
+            ctx.example("passing too", |_| true);
+        });
+        let logger = Arc::new(FileReportLogger::new(directory.clone()));
+        let configuration = ConfigurationBuilder::default()
+            .exit_on_failure(false)
+            .build()
+            .unwrap();
+        let runner = Runner::new(configuration, vec![logger.clone()]);
+        // act
+        let _ = runner.run(&suite);
+        // assert
+        assert!(logger.take_io_error().is_none());
+        let path = logger.last_path().unwrap();
+        assert_eq!(Some(directory.as_path()), path.parent());
+        let json = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(directory.parent().unwrap());
+        assert!(json.starts_with("{\"passed\":2,\"failed\":1,\"ignored\":0,\"duration_ms\":"));
+        assert!(json.contains(",\"report\":{\"id\":\"a suite\""));
+        assert!(json.ends_with("}}"));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+    }
+
+    #[test]
+    fn it_never_overwrites_earlier_reports() {
+        // arrange
+        let directory = temp_dir("file-report-unique");
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        let logger = FileReportLogger::new(directory.clone());
+        let suite = suite("a suite", (), |ctx| {
+            ctx.example("passing", |_| true);
+        });
+        let runner = Runner::new(ConfigurationBuilder::default().build().unwrap(), vec![]);
+        let report = runner.run(&suite);
+        // act
+        let paths: Vec<_> = (0..3)
+            .map(|_| {
+                logger.exit_suite(&runner, &suite.header, &report);
+                logger.last_path().unwrap()
+            })
+            .collect();
+        // assert
+        let written = fs::read_dir(&directory).unwrap().count();
+        let _ = fs::remove_dir_all(&directory);
+        assert!(logger.take_io_error().is_none());
+        assert_eq!(3, written);
+        assert!(paths[0] != paths[1] && paths[1] != paths[2] && paths[0] != paths[2]);
+    }
+
+    #[test]
+    fn it_records_write_errors() {
+        // arrange
+        let blocker = temp_dir("file-report-blocker");
+        fs::write(&blocker, "not a directory").unwrap();
+        let suite = suite("a suite", (), |ctx| {
+            ctx.example("passing", |_| true);
+        });
+        let logger = Arc::new(FileReportLogger::new(blocker.join("reports")));
+        let runner = Runner::new(ConfigurationBuilder::default().build().unwrap(), vec![]);
+        // act
+        logger.exit_suite(&runner, &suite.header, &runner.run(&suite));
+        // assert
+        let _ = fs::remove_file(&blocker);
+        assert!(logger.take_io_error().is_some());
+        assert!(logger.take_io_error().is_none());
+        assert!(logger.last_path().is_none());
+    }
+}
@@ -15,18 +15,44 @@
 //! # }
 //! ```
 
+mod counting;
+mod diff;
+mod failures_only;
+mod file_report;
+mod profile;
 mod serial;
+mod style;
+mod theme;
 
+use std::collections::BTreeMap;
 use std::io;
+use std::sync::{Mutex, MutexGuard};
 
 use header::{ContextHeader, ExampleHeader, SuiteHeader};
 use logger::serial::SerialLogger;
+
+pub use logger::counting::CountingLogger;
+pub use logger::failures_only::FailuresOnlyLogger;
+pub use logger::file_report::FileReportLogger;
+pub use logger::profile::ProfileLogger;
+pub use logger::style::ColorChoice;
+pub use logger::theme::Theme;
 use report::{BlockReport, ContextReport, ExampleReport, SuiteReport};
 use runner::{Runner, RunnerObserver};
 
+/// Top-level blocks of a parallel suite which have been evaluated but not yet logged.
+#[derive(Default)]
+struct PendingBlocks {
+    /// The position of the next block to be logged.
+    next: usize,
+    reports: BTreeMap<usize, BlockReport>,
+}
+
 /// Preferred logger for test suite execution.
 pub struct Logger<T: io::Write> {
     serial: SerialLogger<T>,
+    /// Pending blocks when logging parallel suites incrementally (`None` otherwise).
+    incremental: Option<Mutex<PendingBlocks>>,
 }
 
 impl<T: io::Write> Logger<T>
@@ -36,6 +62,109 @@ where
     pub fn new(buffer: T) -> Logger<T> {
         Logger {
             serial: SerialLogger::new(buffer),
+            incremental: None,
+        }
+    }
+
+    /// Indents each level of nesting by `width` spaces (defaults to 2).
+    pub fn with_indent(self, width: usize) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_indent(width),
+            ..self
+        }
+    }
+
+    /// Drops the decorative blank lines and the `tests:` prefix,
+    /// for embedding the output in that of other tools.
+    pub fn compact(self) -> Logger<T> {
+        Logger {
+            serial: self.serial.compact(),
+            ..self
+        }
+    }
+
+    /// Writes the suite's and contexts' header lines without styling them.
+    pub fn plain_headers(self) -> Logger<T> {
+        Logger {
+            serial: self.serial.plain_headers(),
+            ..self
+        }
+    }
+
+    /// Prefixes each line with the milliseconds elapsed since enabling timestamps.
+    pub fn with_timestamps(self) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_timestamps(),
+            ..self
+        }
+    }
+
+    /// Writes the p50, p90 and p99 of the examples' durations along with the suite's duration.
+    pub fn with_percentiles(self) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_percentiles(),
+            ..self
+        }
+    }
+
+    /// Writes a section grouping the failed examples by their failure message.
+    pub fn with_grouped_failures(self) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_grouped_failures(),
+            ..self
+        }
+    }
+
+    /// Wraps failure messages at word boundaries to lines of at most `width` columns.
+    pub fn with_wrapping(self, width: usize) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_wrapping(width),
+            ..self
+        }
+    }
+
+    /// Words the flags, summary and failures section after the given theme.
+    pub fn with_theme(self, theme: Theme) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_theme(theme),
+            ..self
+        }
+    }
+
+    /// Styles the output according to the given choice (defaults to `ColorChoice::Auto`,
+    /// i.e. styling it unless the `NO_COLOR` environment variable is set).
+    pub fn with_color(self, choice: ColorChoice) -> Logger<T> {
+        Logger {
+            serial: self.serial.with_color(choice),
+            ..self
+        }
+    }
+
+    /// Logs each of a parallel suite's top-level blocks as soon as it and all blocks
+    /// declared before it have been evaluated, rather than logging the whole suite at its end.
+    ///
+    /// Note that this works at the granularity of top-level blocks only: a nested context
+    /// or example is logged along with its top-level block, once that has been evaluated as a whole.
+    pub fn incremental(self) -> Logger<T> {
+        Logger {
+            incremental: Some(Mutex::new(PendingBlocks::default())),
+            ..self
+        }
+    }
+
+    fn lock_pending(&self) -> Option<MutexGuard<'_, PendingBlocks>> {
+        self.incremental.as_ref().map(|pending| {
+            pending
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        })
+    }
+
+    /// Logs the pending blocks up to the first one still being evaluated.
+    fn flush_pending(&self, runner: &Runner, pending: &mut PendingBlocks) {
+        while let Some(report) = pending.reports.remove(&pending.next) {
+            self.replay_block(runner, &report);
+            pending.next += 1;
         }
     }
 
@@ -85,7 +214,12 @@ where
 {
     fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {
         if runner.configuration.parallel {
-            // If the suite is being evaluated in parallel we basically wait for `exit_suite`.
+            // If the suite is being evaluated in parallel we basically wait for `exit_suite`,
+            // unless logging incrementally:
+            if let Some(mut pending) = self.lock_pending() {
+                *pending = PendingBlocks::default();
+                self.serial.enter_suite(runner, header);
+            }
         } else {
             self.serial.enter_suite(runner, header);
         }
@@ -93,6 +227,16 @@ where
 
     fn exit_suite(&self, runner: &Runner, header: &SuiteHeader, report: &SuiteReport) {
         if runner.configuration.parallel {
+            if let Some(mut pending) = self.lock_pending() {
+                // Log whatever blocks are left (e.g. ones skipped due to a failing `before_all`):
+                let blocks = report.get_context().get_blocks();
+                for block in blocks.iter().skip(pending.next) {
+                    self.replay_block(runner, block);
+                }
+                *pending = PendingBlocks::default();
+                self.serial.exit_suite(runner, header, report);
+                return;
+            }
             // If the suite is being evaluated in parallel and we have reached the end of it,
             // then it is time to forward a replay of the events to the inner serial logger:
             self.replay_suite(runner, header, report);
@@ -132,4 +276,241 @@ where
             self.serial.exit_example(runner, header, report);
         }
     }
+
+    fn exit_suite_block(&self, runner: &Runner, index: usize, report: &BlockReport) {
+        if !runner.configuration.parallel {
+            return;
+        }
+        if let Some(mut pending) = self.lock_pending() {
+            pending.reports.insert(index, report.clone());
+            self.flush_pending(runner, &mut pending);
+        }
+    }
+
+    fn enter_session(&self, runner: &Runner) {
+        self.serial.enter_session(runner);
+    }
+
+    fn exit_session(&self, runner: &Runner, report: &SuiteReport) {
+        self.serial.exit_session(runner, report);
+    }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.serial.take_io_error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use header::{ExampleLabel, SuiteLabel};
+    use runner::ConfigurationBuilder;
+
+    /// A buffer that can still be read after having been handed to a logger.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    mod incremental {
+        use super::*;
+
+        fn example(name: &'static str) -> BlockReport {
+            let header = ExampleHeader::new(ExampleLabel::It, name);
+            BlockReport::Example(header, ExampleReport::default())
+        }
+
+        #[test]
+        fn it_logs_blocks_in_declaration_order() {
+            // arrange
+            let buffer = SharedBuffer::default();
+            let logger = Logger::new(buffer.clone()).incremental();
+            let configuration = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![]);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            logger.enter_suite(&runner, &header);
+            // act
+            logger.exit_suite_block(&runner, 1, &example("second"));
+            let before_first = buffer.contents();
+            logger.exit_suite_block(&runner, 0, &example("first"));
+            // assert
+            assert!(!before_first.contains("second"));
+            let output = buffer.contents();
+            let first = output.find("\"first\"").expect("first to be logged");
+            let second = output.find("\"second\"").expect("second to be logged");
+            assert!(first < second);
+        }
+
+        #[test]
+        fn it_logs_the_whole_suite_in_order() {
+            // arrange
+            let buffer = SharedBuffer::default();
+            let logger = Arc::new(Logger::new(buffer.clone()).incremental());
+            let configuration = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger]);
+            let suite = ::block::suite("a suite", (), |ctx| {
+                for &name in &["a", "b", "c", "d"] {
+                    ctx.context(name, |ctx| {
+                        ctx.it("works", |_| {});
+                    });
+                }
+            });
+            // act
+            let _ = runner.run(&suite);
+            // assert
+            let output = buffer.contents();
+            let positions: Vec<_> = ["\"a\"", "\"b\"", "\"c\"", "\"d\""]
+                .iter()
+                .map(|name| output.find(name).expect("context to be logged"))
+                .collect();
+            let mut sorted = positions.clone();
+            sorted.sort();
+            assert_eq!(sorted, positions);
+            assert!(output.contains("test result: ok. 4 passed"));
+        }
+
+        #[test]
+        fn it_logs_nested_blocks_along_with_their_top_level_block() {
+            // arrange
+            let buffer = SharedBuffer::default();
+            let logger = Arc::new(Logger::new(buffer.clone()).incremental());
+            let configuration = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger]);
+            let logged_while_nested = Arc::new(Mutex::new(vec![]));
+            let (nested_buffer, nested_logged) = (buffer.clone(), logged_while_nested.clone());
+            let suite = ::block::suite("a suite", (), move |ctx| {
+                ctx.context("outer", move |ctx| {
+                    ctx.context("first", |ctx| {
+                        ctx.it("works", |_| {});
+                    });
+                    ctx.context("second", move |ctx| {
+                        ctx.it("works", move |_| {
+                            let logged = nested_buffer.contents();
+                            nested_logged.lock().unwrap().push(logged);
+                        });
+                    });
+                });
+            });
+            // act
+            let _ = runner.run(&suite);
+            // assert
+            let logged_while_nested = logged_while_nested.lock().unwrap();
+            assert!(!logged_while_nested[0].contains("\"outer\""));
+            let output = buffer.contents();
+            let positions: Vec<_> = ["\"outer\"", "\"first\"", "\"second\""]
+                .iter()
+                .map(|name| output.find(name).expect("context to be logged"))
+                .collect();
+            let mut sorted = positions.clone();
+            sorted.sort();
+            assert_eq!(sorted, positions);
+        }
+    }
+
+    mod session {
+        use super::*;
+
+        use block::{describe, given, suite};
+        use report::Report;
+
+        #[test]
+        fn it_writes_a_single_prefix_and_summary() {
+            // arrange
+            let buffer = SharedBuffer::default();
+            let logger = Arc::new(Logger::new(buffer.clone()));
+            let configuration = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger]);
+            // act
+            runner.begin_session();
+            let _ = runner.run(&suite("a suite", (), |ctx| {
+                ctx.example("passes", |_| true);
+            }));
+            let _ = runner.run(&describe("a description", (), |ctx| {
+                ctx.it("passes", |_| true);
+            }));
+            let _ = runner.run(&given("a scenario", (), |ctx| {
+                ctx.then("fails", |_| false);
+            }));
+            let report = runner.end_session();
+            // assert
+            let output = buffer.contents();
+            assert_eq!(1, output.matches("tests:").count());
+            assert_eq!(1, output.matches("test result:").count());
+            assert!(output.contains("test result: FAILED. 2 passed; 1 failed; 0 ignored; 3 total"));
+            assert!(output.contains("Suite \"a suite\""));
+            assert!(output.contains("Given \"a scenario\""));
+            assert_eq!((2, 1), (report.get_passed(), report.get_failed()));
+            assert_eq!(
+                vec![vec!["a scenario".to_owned(), "fails".to_owned()]],
+                report.failed_examples()
+            );
+            assert!(!runner.in_session());
+        }
+    }
+
+    mod veto {
+        use super::*;
+
+        use block::suite;
+
+        struct VetoingObserver;
+
+        impl RunnerObserver for VetoingObserver {
+            fn finalize(&self, _report: &SuiteReport) -> bool {
+                false
+            }
+        }
+
+        #[test]
+        fn it_sums_up_vetoed_runs_as_failed() {
+            // arrange
+            let buffer = SharedBuffer::default();
+            let logger = Arc::new(Logger::new(buffer.clone()));
+            let configuration = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let observers: Vec<Arc<dyn RunnerObserver>> = vec![Arc::new(VetoingObserver), logger];
+            let runner = Runner::new(configuration, observers);
+            // act
+            let result = runner.try_run(&suite("a suite", (), |ctx| {
+                ctx.example("passes", |_| true);
+            }));
+            // assert
+            let output = buffer.contents();
+            assert!(result.is_err());
+            assert!(output.contains("test result: FAILED. 1 passed; 0 failed; 0 ignored; 1 total"));
+            assert!(output.contains("error: vetoed by an observer"));
+            assert!(!output.contains("test result: ok"));
+        }
+    }
 }
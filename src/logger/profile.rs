@@ -0,0 +1,154 @@
+use std::cmp::Reverse;
+use std::io;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use header::SuiteHeader;
+use report::{BlockReport, ContextReport, Report, SuiteReport};
+use runner::{Runner, RunnerObserver};
+
+/// Logger listing the slowest contexts and examples of a test suite once it has been evaluated.
+pub struct ProfileLogger<T: io::Write = io::Stdout> {
+    buffer: Mutex<T>,
+    error: Mutex<Option<io::Error>>,
+    limit: usize,
+}
+
+impl Default for ProfileLogger<io::Stdout> {
+    fn default() -> Self {
+        ProfileLogger::new(io::stdout(), 10)
+    }
+}
+
+impl<T: io::Write> ProfileLogger<T> {
+    /// Creates a logger listing the `limit` slowest contexts and examples each.
+    pub fn new(buffer: T, limit: usize) -> Self {
+        ProfileLogger {
+            buffer: Mutex::new(buffer),
+            error: Mutex::new(None),
+            limit,
+        }
+    }
+
+    fn write_profile<W: io::Write>(&self, buffer: &mut W, report: &SuiteReport) -> io::Result<()> {
+        let mut contexts = vec![];
+        let mut examples = vec![];
+        Self::collect(report.get_context(), &mut contexts, &mut examples);
+        self.write_top_list(buffer, "contexts", contexts)?;
+        self.write_top_list(buffer, "examples", examples)?;
+        buffer.flush()
+    }
+
+    fn write_top_list<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        kind: &str,
+        mut entries: Vec<(String, Duration)>,
+    ) -> io::Result<()> {
+        // Sorting is stable, so equally slow entries remain in declaration order:
+        entries.sort_by_key(|&(_, duration)| Reverse(duration));
+        entries.truncate(self.limit);
+        writeln!(buffer, "\nTop {} slowest {}:", entries.len(), kind)?;
+        for (name, duration) in entries {
+            writeln!(buffer, "  {} ({}ms)", name, duration.as_millis())?;
+        }
+        Ok(())
+    }
+
+    fn collect(
+        report: &ContextReport,
+        contexts: &mut Vec<(String, Duration)>,
+        examples: &mut Vec<(String, Duration)>,
+    ) {
+        for block in report.get_blocks() {
+            match block {
+                BlockReport::Context(ref header, ref report) => {
+                    if let Some(ref header) = *header {
+                        contexts.push((header.to_string(), report.get_duration()));
+                    }
+                    Self::collect(report, contexts, examples);
+                }
+                BlockReport::Example(ref header, ref report) => {
+                    examples.push((header.to_string(), report.get_duration()));
+                }
+            }
+        }
+    }
+}
+
+impl<T: io::Write> RunnerObserver for ProfileLogger<T>
+where
+    T: Send + Sync,
+{
+    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+        let mut buffer = self
+            .buffer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(error) = self.write_profile(&mut *buffer, report) {
+            let mut first_error = self
+                .error
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if first_error.is_none() {
+                *first_error = Some(error);
+            }
+        }
+    }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::{ContextHeader, ContextLabel, ExampleHeader, ExampleLabel, SuiteLabel};
+    use report::{ExampleReport, ExampleResult};
+
+    fn example(name: &'static str, millis: u64) -> BlockReport {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        let report = ExampleReport::new(ExampleResult::Success, Duration::from_millis(millis));
+        BlockReport::Example(header, report)
+    }
+
+    fn context(name: &'static str, millis: u64, blocks: Vec<BlockReport>) -> BlockReport {
+        let header = ContextHeader::new(ContextLabel::Context, name);
+        let report = ContextReport::new(blocks, Duration::from_millis(millis));
+        BlockReport::Context(Some(header), report)
+    }
+
+    #[test]
+    fn it_lists_the_slowest_blocks_first() {
+        // arrange
+        let logger = ProfileLogger::new(vec![], 2);
+        let root = ContextReport::new(
+            vec![
+                context("fast", 10, vec![example("a", 3), example("b", 7)]),
+                context("slow", 30, vec![example("c", 30)]),
+                context("medium", 20, vec![example("d", 5), example("e", 15)]),
+            ],
+            Duration::from_millis(60),
+        );
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root);
+        let mut buffer = vec![];
+        // act
+        logger.write_profile(&mut buffer, &report).unwrap();
+        // assert
+        let expected = "
+Top 2 slowest contexts:
+  Context \"slow\" (30ms)
+  Context \"medium\" (20ms)
+
+Top 2 slowest examples:
+  It \"c\" (30ms)
+  It \"e\" (15ms)
+";
+        assert_eq!(expected, String::from_utf8(buffer).unwrap());
+    }
+}
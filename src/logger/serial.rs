@@ -1,26 +1,116 @@
 use std::io;
+use std::io::Write;
 use std::ops::DerefMut;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 
-use time::Duration;
+use std::time::{Duration, Instant};
 
-use colored::*;
+use logger::diff::{diff_lines, DiffLine};
+use logger::style::{ColorChoice, Colorize, StyledString};
+use logger::theme::Theme;
 
 use header::{ContextHeader, ExampleHeader, SuiteHeader};
 use report::{BlockReport, ContextReport, ExampleReport, ExampleResult, Report, SuiteReport};
-use runner::{Runner, RunnerObserver};
+use runner::{Layout, Runner, RunnerObserver};
 
 #[derive(new)]
 struct SerialLoggerState<T: io::Write = io::Stdout> {
     buffer: T,
     #[new(value = "0")]
     level: usize,
+    /// The names of the suite and contexts currently being evaluated.
+    #[new(default)]
+    path: Vec<String>,
+    /// The first error encountered while writing to the buffer (if any).
+    #[new(default)]
+    error: Option<io::Error>,
+    /// When the logger started timestamping lines (`None` if it doesn't).
+    #[new(default)]
+    timestamps_since: Option<Instant>,
+    /// Whether the next byte written starts a new line.
+    #[new(value = "true")]
+    at_line_start: bool,
+}
+
+/// Writes to the buffer, prefixing each line with a timestamp if enabled.
+impl<T: io::Write> io::Write for SerialLoggerState<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let since = match self.timestamps_since {
+            Some(since) => since,
+            None => return self.buffer.write(buf),
+        };
+        // Only ever write up to the end of the first line, so the next line gets its own timestamp:
+        let line_end = buf.iter().position(|&byte| byte == b'\n');
+        let line = line_end.map_or(buf, |end| &buf[..=end]);
+        if self.at_line_start && !line.is_empty() {
+            write!(self.buffer, "[{:>8}ms] ", since.elapsed().as_millis())?;
+            self.at_line_start = false;
+        }
+        let written = self.buffer.write(line)?;
+        if line_end.is_some() && written == line.len() {
+            self.at_line_start = true;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.buffer.flush()
+    }
+}
+
+/// The nearest-rank `percent`-th percentile of the (non-empty, ascending) durations.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+/// Breaks each of the text's lines into lines of at most `width` characters at whitespace,
+/// moving words that would exceed it onto a line of their own.
+///
+/// Each line keeps its leading whitespace (also for the lines it gets broken into)
+/// and the spacing between its words, e.g. for indented listings within messages.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    for text_line in text.lines() {
+        let content = text_line.trim_start();
+        let indent = &text_line[..text_line.len() - content.len()];
+        let mut line = indent.to_owned();
+        let mut has_words = false;
+        let (mut gap, mut rest) = ("", content);
+        while !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let (word, after) = rest.split_at(end);
+            let length = line.chars().count() + gap.chars().count() + word.chars().count();
+            if has_words && length > width {
+                lines.push(line);
+                line = indent.to_owned();
+            } else {
+                line.push_str(gap);
+            }
+            line.push_str(word);
+            has_words = true;
+            rest = after.trim_start();
+            gap = &after[..after.len() - rest.len()];
+        }
+        lines.push(line);
+    }
+    lines
 }
 
 /// Preferred logger for serial test suite execution
 /// (see [`Configuration.parallel`](struct.Configuration.html#fields)).
 pub struct SerialLogger<T: io::Write = io::Stdout> {
     state: Mutex<SerialLoggerState<T>>,
+    indent_width: usize,
+    compact: bool,
+    styled_headers: bool,
+    percentiles: bool,
+    grouped_failures: bool,
+    /// The column at which to wrap failure messages (if at all).
+    wrap_width: Option<usize>,
+    /// Whether to style the output, as resolved from the color choice upon construction.
+    colorize: bool,
+    theme: Theme,
 }
 
 impl Default for SerialLogger<io::Stdout> {
@@ -34,41 +124,149 @@ impl<T: io::Write> SerialLogger<T> {
         let state = SerialLoggerState::new(buffer);
         SerialLogger {
             state: Mutex::new(state),
+            indent_width: 2,
+            compact: false,
+            styled_headers: true,
+            percentiles: false,
+            grouped_failures: false,
+            wrap_width: None,
+            colorize: ColorChoice::Auto.resolve(),
+            theme: Theme::default(),
         }
     }
 
-    fn padding(depth: usize) -> String {
-        "  ".repeat(depth)
+    /// Indents each level of nesting by `width` spaces (defaults to 2).
+    pub fn with_indent(mut self, width: usize) -> Self {
+        self.indent_width = width;
+        self
+    }
+
+    /// Drops the decorative blank lines and the `tests:` prefix,
+    /// for embedding the output in that of other tools.
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    /// Writes the suite's and contexts' header lines without styling them
+    /// (which they otherwise are, unless colors are disabled).
+    pub fn plain_headers(mut self) -> Self {
+        self.styled_headers = false;
+        self
+    }
+
+    /// Writes the p50, p90 and p99 of the examples' durations along with the suite's duration.
+    pub fn with_percentiles(mut self) -> Self {
+        self.percentiles = true;
+        self
+    }
+
+    /// Writes a section grouping the failed examples by their failure message,
+    /// for suites failing many examples for the same reason.
+    pub fn with_grouped_failures(mut self) -> Self {
+        self.grouped_failures = true;
+        self
+    }
+
+    /// Wraps failure messages at word boundaries to lines of at most `width` columns
+    /// (including their indentation), unless a single word exceeds it.
+    pub fn with_wrapping(mut self, width: usize) -> Self {
+        self.wrap_width = Some(width);
+        self
+    }
+
+    /// Words the flags, summary and failures section after the given theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Styles the output according to the given choice (defaults to `ColorChoice::Auto`).
+    pub fn with_color(mut self, choice: ColorChoice) -> Self {
+        self.colorize = choice.resolve();
+        self
+    }
+
+    /// The styled string, or its plain text if not styling the output.
+    pub(super) fn paint(&self, styled: StyledString) -> StyledString {
+        if self.colorize {
+            styled
+        } else {
+            styled.clear()
+        }
+    }
+
+    fn suite_header(&self, header: &SuiteHeader) -> StyledString {
+        if self.styled_headers {
+            self.paint(header.to_string().bold())
+        } else {
+            header.to_string().normal()
+        }
+    }
+
+    fn context_header(&self, header: &ContextHeader) -> StyledString {
+        if self.styled_headers {
+            self.paint(header.to_string().cyan())
+        } else {
+            header.to_string().normal()
+        }
+    }
+
+    /// The blank line separating sections, unless compact.
+    fn gap(&self) -> &'static str {
+        if self.compact {
+            ""
+        } else {
+            "\n"
+        }
+    }
+
+    fn padding(&self, depth: usize) -> String {
+        " ".repeat(self.indent_width * depth)
+    }
+
+    /// Prefixes each line with the milliseconds elapsed since enabling timestamps,
+    /// for correlating the output with that of other tools.
+    pub fn with_timestamps(self) -> Self {
+        self.lock_state().timestamps_since = Some(Instant::now());
+        self
+    }
+
+    /// Takes the first error encountered while writing to the buffer, if any.
+    pub fn take_io_error(&self) -> Option<io::Error> {
+        self.lock_state().error.take()
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, SerialLoggerState<T>> {
+        // A panic while holding the lock can't leave the state inconsistent in a harmful way:
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     fn access_state<F>(&self, mut accessor: F)
     where
         F: FnMut(&mut SerialLoggerState<T>) -> io::Result<()>,
     {
-        if let Ok(ref mut mutex_guard) = self.state.lock() {
-            let result = accessor(mutex_guard.deref_mut());
-            if let Err(error) = result {
-                // TODO: better error handling
-                eprintln!("\n{}: {:?}", "error".red().bold(), error);
+        let mut mutex_guard = self.lock_state();
+        let state = mutex_guard.deref_mut();
+        if let Err(error) = accessor(state) {
+            // Only the first error is kept, as any later ones are most likely caused by it:
+            if state.error.is_none() {
+                state.error = Some(error);
             }
-        } else {
-            // TODO: better error handling
-            eprintln!(
-                "\n{}: failed to aquire lock on mutex.",
-                "error".red().bold()
-            );
         }
     }
 
-    fn write_suite_failures(
+    fn write_suite_failures<W: io::Write>(
         &self,
-        buffer: &mut T,
+        buffer: &mut W,
         indent: usize,
         report: &SuiteReport,
     ) -> io::Result<()> {
         if report.is_failure() {
-            let _ = writeln!(buffer, "\nfailures:\n");
-            writeln!(buffer, "{}{}", Self::padding(indent), report.get_header())?;
+            let _ = writeln!(buffer, "{0}{1}:{0}", self.gap(), self.theme.failures_header);
+            writeln!(buffer, "{}{}", self.padding(indent), report.get_header())?;
             let context_report = report.get_context();
             for block_report in context_report.get_blocks() {
                 self.write_block_failures(buffer, indent + 1, block_report)?;
@@ -78,22 +276,33 @@ impl<T: io::Write> SerialLogger<T> {
         Ok(())
     }
 
-    fn write_block_failures(
+    fn write_block_failures<W: io::Write>(
         &self,
-        buffer: &mut T,
+        buffer: &mut W,
         indent: usize,
         report: &BlockReport,
     ) -> io::Result<()> {
         if report.is_failure() {
             match report {
                 BlockReport::Context(ref header, ref report) => {
-                    if let Some(header) = header.as_ref() {
-                        write!(buffer, "{}{}", Self::padding(indent), header)?;
-                    }
-                    self.write_context_failures(buffer, indent + 1, report)?;
+                    let indent = match header.as_ref() {
+                        Some(header) => {
+                            writeln!(buffer, "{}{}", self.padding(indent), header)?;
+                            indent + 1
+                        }
+                        // Name-less scopes get neither a line nor a level of indentation:
+                        None => indent,
+                    };
+                    self.write_context_failures(buffer, indent, report)?;
                 }
                 BlockReport::Example(ref header, ref report) => {
-                    writeln!(buffer, "{}{}", Self::padding(indent), header)?;
+                    write!(buffer, "{}{}", self.padding(indent), header)?;
+                    match header.location() {
+                        Some(location) => {
+                            writeln!(buffer, " ({}:{})", location.file(), location.line())?
+                        }
+                        None => writeln!(buffer)?,
+                    }
                     self.write_example_failure(buffer, indent + 1, report)?;
                 }
             }
@@ -101,68 +310,279 @@ impl<T: io::Write> SerialLogger<T> {
         Ok(())
     }
 
-    fn write_context_failures(
+    fn write_context_failures<W: io::Write>(
         &self,
-        buffer: &mut T,
+        buffer: &mut W,
         indent: usize,
         report: &ContextReport,
     ) -> io::Result<()> {
         if report.is_failure() {
-            writeln!(buffer)?;
             for block_report in report.get_blocks() {
-                self.write_block_failures(buffer, indent + 1, block_report)?;
+                self.write_block_failures(buffer, indent, block_report)?;
             }
         }
 
         Ok(())
     }
 
-    fn write_example_failure(
+    pub(super) fn write_example_failure<W: io::Write>(
         &self,
-        buffer: &mut T,
+        buffer: &mut W,
         indent: usize,
         report: &ExampleReport,
     ) -> io::Result<()> {
-        if let ExampleResult::Failure(Some(ref reason)) = report.get_result() {
-            let padding = Self::padding(indent);
-            writeln!(buffer, "{}{}", padding, reason)?;
+        if let ExampleResult::Failure {
+            ref message,
+            ref expected,
+            ref actual,
+        } = report.get_result()
+        {
+            let padding = self.padding(indent);
+            match (message, self.wrap_width) {
+                (Some(ref message), Some(width)) => {
+                    let width = width.saturating_sub(padding.len());
+                    for line in wrap(message, width) {
+                        writeln!(buffer, "{}{}", padding, line)?;
+                    }
+                }
+                (Some(ref message), None) => writeln!(buffer, "{}{}", padding, message)?,
+                (None, _) => {}
+            }
+            if let (Some(ref expected), Some(ref actual)) = (expected, actual) {
+                self.write_diff(buffer, indent, expected, actual)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_diff<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        indent: usize,
+        expected: &str,
+        actual: &str,
+    ) -> io::Result<()> {
+        let padding = self.padding(indent);
+        writeln!(
+            buffer,
+            "{}{} / {}",
+            padding,
+            self.paint("- expected".red()),
+            self.paint("+ actual".green())
+        )?;
+        for line in diff_lines(expected, actual) {
+            match line {
+                DiffLine::Same(line) => writeln!(buffer, "{}  {}", padding, line)?,
+                DiffLine::Removed(line) => writeln!(
+                    buffer,
+                    "{}{}",
+                    padding,
+                    self.paint(format!("- {}", line).red())
+                )?,
+                DiffLine::Added(line) => writeln!(
+                    buffer,
+                    "{}{}",
+                    padding,
+                    self.paint(format!("+ {}", line).green())
+                )?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_suite_prefix<W: io::Write>(&self, buffer: &mut W, runner: &Runner) -> io::Result<()> {
+        if let Some(seed) = runner.seed() {
+            writeln!(buffer, "{}Randomized with seed {}", self.gap(), seed)?;
+        }
+        // Sessions get a single `tests:` prefix of their own:
+        if !runner.in_session() {
+            self.write_tests_prefix(buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_tests_prefix<W: io::Write>(&self, buffer: &mut W) -> io::Result<()> {
+        if !self.compact {
+            writeln!(buffer, "\ntests:\n")?;
         }
+
         Ok(())
     }
 
-    fn write_suite_prefix(&self, buffer: &mut T) -> io::Result<()> {
-        writeln!(buffer, "\ntests:\n")?;
+    fn write_failing_contexts<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        report: &SuiteReport,
+    ) -> io::Result<()> {
+        let mut failing_contexts = vec![];
+        Self::collect_failing_contexts(report.get_context(), &mut vec![], &mut failing_contexts);
+        if failing_contexts.is_empty() {
+            return Ok(());
+        }
+        writeln!(buffer, "{0}failing contexts:{0}", self.gap())?;
+        for (path, report) in failing_contexts {
+            writeln!(
+                buffer,
+                "{}{}: {} of {} failed",
+                self.padding(1),
+                path.join(" > "),
+                report.get_failed(),
+                report.get_total()
+            )?;
+        }
+        Ok(())
+    }
 
+    fn write_grouped_failures<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        report: &SuiteReport,
+    ) -> io::Result<()> {
+        // Grouped by message, in the order of each message's first failure:
+        let mut groups: Vec<(Option<String>, Vec<Vec<String>>)> = vec![];
+        for failure in report.failures() {
+            match groups
+                .iter_mut()
+                .find(|(message, _)| *message == failure.message)
+            {
+                Some((_, paths)) => paths.push(failure.path),
+                None => groups.push((failure.message, vec![failure.path])),
+            }
+        }
+        if groups.is_empty() {
+            return Ok(());
+        }
+        writeln!(buffer, "{0}grouped failures:{0}", self.gap())?;
+        for (message, paths) in groups {
+            let examples = if paths.len() == 1 {
+                "example"
+            } else {
+                "examples"
+            };
+            writeln!(
+                buffer,
+                "{}{} {} failed with: {}",
+                self.padding(1),
+                paths.len(),
+                examples,
+                message.as_ref().map_or("(no message)", String::as_str)
+            )?;
+            for path in paths {
+                writeln!(buffer, "{}{}", self.padding(2), path.join(" > "))?;
+            }
+        }
         Ok(())
     }
 
-    fn write_suite_suffix(&self, buffer: &mut T, report: &SuiteReport) -> io::Result<()> {
-        self.write_duration(buffer, report.get_duration())?;
+    fn collect_failing_contexts<'a>(
+        report: &'a ContextReport,
+        path: &mut Vec<&'a str>,
+        failing_contexts: &mut Vec<(Vec<&'a str>, &'a ContextReport)>,
+    ) {
+        for block in report.get_blocks() {
+            if let BlockReport::Context(ref header, ref report) = *block {
+                if !report.is_failure() {
+                    continue;
+                }
+                match *header {
+                    Some(ref header) => {
+                        path.push(&header.name);
+                        failing_contexts.push((path.clone(), report));
+                        Self::collect_failing_contexts(report, path, failing_contexts);
+                        path.pop();
+                    }
+                    None => Self::collect_failing_contexts(report, path, failing_contexts),
+                }
+            }
+        }
+    }
+
+    pub(super) fn write_suite_suffix<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        report: &SuiteReport,
+    ) -> io::Result<()> {
+        self.write_failing_contexts(buffer, report)?;
+        if self.grouped_failures {
+            self.write_grouped_failures(buffer, report)?;
+        }
+        writeln!(
+            buffer,
+            "{}duration: {}.",
+            self.gap(),
+            Self::format_duration(report.get_wall_duration())
+        )?;
+        if let Some(elapsed) = report.get_elapsed() {
+            writeln!(buffer, "finished in {}.", Self::format_duration(elapsed))?;
+        }
+        if self.percentiles {
+            self.write_percentiles(buffer, report)?;
+        }
 
-        write!(buffer, "\ntest result: {}.", self.report_flag(report))?;
+        write!(
+            buffer,
+            "{}{}: {}.",
+            self.gap(),
+            self.theme.summary_prefix,
+            self.report_flag(report)
+        )?;
 
         writeln!(
             buffer,
-            " {} passed; {} failed; {} ignored",
+            " {} passed; {} failed; {} ignored; {} total",
             report.get_passed(),
             report.get_failed(),
-            report.get_ignored()
+            report.get_ignored(),
+            report.get_total()
         )?;
 
+        if let Some(error) = report.get_error() {
+            let label = self.paint("error".red().bold());
+            writeln!(buffer, "{}{}: {}", self.gap(), label, error)?;
+        }
+
         if report.is_failure() {
-            writeln!(buffer, "\n{}: test failed", "error".red().bold())?;
+            writeln!(
+                buffer,
+                "{}{}: test failed",
+                self.gap(),
+                self.paint("error".red().bold())
+            )?;
         }
 
         Ok(())
     }
 
-    fn write_duration(&self, buffer: &mut T, duration: Duration) -> io::Result<()> {
+    fn write_percentiles<W: io::Write>(
+        &self,
+        buffer: &mut W,
+        report: &SuiteReport,
+    ) -> io::Result<()> {
+        let mut durations = vec![];
+        report
+            .get_context()
+            .collect_example_durations(&mut durations);
+        if durations.is_empty() {
+            return Ok(());
+        }
+        durations.sort();
+        writeln!(
+            buffer,
+            "percentiles: p50 {}, p90 {}, p99 {}.",
+            Self::format_duration(percentile(&durations, 50)),
+            Self::format_duration(percentile(&durations, 90)),
+            Self::format_duration(percentile(&durations, 99))
+        )
+    }
+
+    fn format_duration(duration: Duration) -> String {
         let millisecond = 1;
         let second = 1000 * millisecond;
         let minute = 60 * second;
         let hour = 60 * minute;
 
-        let remainder = duration.whole_milliseconds();
+        let remainder = duration.as_millis();
 
         let hours = remainder / hour;
         let remainder = remainder % hour;
@@ -175,21 +595,41 @@ impl<T: io::Write> SerialLogger<T> {
 
         let milliseconds = remainder / millisecond;
         match (hours, minutes, seconds, milliseconds) {
-            (0, 0, s, ms) => writeln!(buffer, "\nduration: {}.{:03}s.", s, ms),
-            (0, m, s, ms) => writeln!(buffer, "\nduration: {}m {}.{:03}s.", m, s, ms),
-            (h, m, s, ms) => writeln!(buffer, "\nduration: {}h {}m {}.{:03}s.", h, m, s, ms),
+            (0, 0, s, ms) => format!("{}.{:03}s", s, ms),
+            (0, m, s, ms) => format!("{}m {}.{:03}s", m, s, ms),
+            (h, m, s, ms) => format!("{}h {}m {}.{:03}s", h, m, s, ms),
+        }
+    }
+
+    fn attempts_note(&self, report: &ExampleReport) -> String {
+        let retries = report.get_attempts() - 1;
+        let plural = if retries == 1 { "retry" } else { "retries" };
+        match retries {
+            0 => String::new(),
+            _ if report.is_success() => format!(" (passed after {} {})", retries, plural),
+            _ => format!(" (failed despite {} {})", retries, plural),
         }
     }
 
-    fn report_flag<R>(&self, report: &R) -> ColoredString
+    fn ignored_note(&self, report: &ExampleReport) -> String {
+        match report.get_result() {
+            ExampleResult::Ignored(Some(ref reason)) => format!(" ({})", reason),
+            _ => String::new(),
+        }
+    }
+
+    fn report_flag<R>(&self, report: &R) -> StyledString
     where
         R: Report,
     {
-        if report.is_success() {
-            "ok".green()
+        let flag = if report.is_failure() {
+            self.theme.failure.as_str().red()
+        } else if report.get_passed() == 0 && report.get_ignored() > 0 {
+            self.theme.ignored.as_str().yellow()
         } else {
-            "FAILED".red()
-        }
+            self.theme.success.as_str().green()
+        };
+        self.paint(flag)
     }
 }
 
@@ -197,53 +637,101 @@ impl<T: io::Write> RunnerObserver for SerialLogger<T>
 where
     T: Send + Sync,
 {
-    fn enter_suite(&self, _runner: &Runner, header: &SuiteHeader) {
+    fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {
         self.access_state(|state| {
             state.level += 1;
-            self.write_suite_prefix(&mut state.buffer)?;
-            writeln!(state.buffer, "{}{}", Self::padding(state.level - 1), header)?;
+            state.path = vec![header.name.to_string()];
+            self.write_suite_prefix(state, runner)?;
+            if runner.configuration.output_layout == Layout::Tree {
+                writeln!(
+                    state,
+                    "{}{}",
+                    self.padding(state.level - 1),
+                    self.suite_header(header)
+                )?;
+            }
 
             Ok(())
         });
     }
 
-    fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
+    fn exit_suite(&self, runner: &Runner, _header: &SuiteHeader, report: &SuiteReport) {
         self.access_state(|state| {
-            self.write_suite_failures(&mut state.buffer, 0, report)?;
-            self.write_suite_suffix(&mut state.buffer, report)?;
-
             state.level -= 1;
+            state.path.clear();
+
+            // Sessions get a single, combined summary of their own:
+            if !runner.in_session() {
+                self.write_suite_failures(state, 0, report)?;
+                self.write_suite_suffix(state, report)?;
+            }
+            state.flush()?;
 
             Ok(())
         });
     }
 
-    fn enter_context(&self, _runner: &Runner, header: &ContextHeader) {
+    fn enter_session(&self, _runner: &Runner) {
+        self.access_state(|state| self.write_tests_prefix(state));
+    }
+
+    fn exit_session(&self, _runner: &Runner, report: &SuiteReport) {
+        self.access_state(|state| {
+            self.write_suite_failures(state, 0, report)?;
+            self.write_suite_suffix(state, report)?;
+            state.flush()
+        });
+    }
+
+    fn enter_context(&self, runner: &Runner, header: &ContextHeader) {
         self.access_state(|state| {
             state.level += 1;
-            writeln!(state.buffer, "{}{}", Self::padding(state.level - 1), header)?;
+            state.path.push(header.name.to_string());
+            if runner.configuration.output_layout == Layout::Tree {
+                writeln!(
+                    state,
+                    "{}{}",
+                    self.padding(state.level - 1),
+                    self.context_header(header)
+                )?;
+            }
 
             Ok(())
         });
     }
 
-    fn exit_context(&self, _runner: &Runner, _header: &ContextHeader, _report: &ContextReport) {
+    fn exit_context(&self, runner: &Runner, header: &ContextHeader, report: &ContextReport) {
         self.access_state(|state| {
+            if report.get_total() == 0 {
+                let padding = match runner.configuration.output_layout {
+                    Layout::Tree => self.padding(state.level),
+                    Layout::Flat => String::new(),
+                };
+                writeln!(
+                    state,
+                    "{}{}: {} contains no examples",
+                    padding,
+                    self.paint("warning".yellow().bold()),
+                    header
+                )?;
+            }
             state.level -= 1;
+            state.path.pop();
 
             Ok(())
         });
     }
 
-    fn enter_example(&self, _runner: &Runner, header: &ExampleHeader) {
+    fn enter_example(&self, runner: &Runner, header: &ExampleHeader) {
         self.access_state(|state| {
             state.level += 1;
-            write!(
-                state.buffer,
-                "{}{} ... ",
-                Self::padding(state.level - 1),
-                header
-            )?;
+            match runner.configuration.output_layout {
+                Layout::Tree => write!(state, "{}{} ... ", self.padding(state.level - 1), header)?,
+                Layout::Flat => {
+                    let path = state.path.join(" > ");
+                    write!(state, "{} > {} ... ", path, header.name)?
+                }
+            }
 
             Ok(())
         });
@@ -251,18 +739,32 @@ where
 
     fn exit_example(&self, _runner: &Runner, _header: &ExampleHeader, report: &ExampleReport) {
         self.access_state(|state| {
-            writeln!(state.buffer, "{}", self.report_flag(report))?;
             state.level -= 1;
+            writeln!(
+                state,
+                "{}{}",
+                self.report_flag(report),
+                self.attempts_note(report) + &self.ignored_note(report)
+            )?;
 
             Ok(())
         });
     }
+
+    fn take_io_error(&self) -> Option<io::Error> {
+        SerialLogger::take_io_error(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use block::{suite, Suite};
+    use header::{ContextLabel, ExampleLabel, SuiteLabel};
+    use runner::{Configuration, ConfigurationBuilder};
+    use std::sync::Arc;
+
     mod padding {
         use super::*;
 
@@ -272,10 +774,1008 @@ mod tests {
             let expected = vec![("", 0), ("  ", 1), ("    ", 2), ("      ", 3)];
             for (expected_res, given_depth) in expected {
                 // act
-                let res = SerialLogger::<Vec<u8>>::padding(given_depth);
+                let res = SerialLogger::new(vec![]).padding(given_depth);
                 // assert
                 assert_eq!(String::from(expected_res), res)
             }
         }
+
+        #[test]
+        fn it_padds_nested_contexts_with_custom_indent() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_indent(4);
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let suite_header = SuiteHeader::new(SuiteLabel::Suite, "suite");
+            let context_header = ContextHeader::new(ContextLabel::Context, "nested");
+            // act
+            logger.enter_suite(&runner, &suite_header);
+            logger.enter_context(&runner, &context_header);
+            logger.enter_context(&runner, &context_header);
+            // assert
+            let state = logger.lock_state();
+            let output = String::from_utf8(state.buffer.clone()).unwrap();
+            assert!(output.contains("\n    Context \"nested\"\n"));
+            assert!(output.contains("\n        Context \"nested\"\n"));
+        }
+    }
+
+    #[cfg(not(feature = "color"))]
+    mod without_color {
+        use super::*;
+
+        #[test]
+        fn it_writes_plain_flags() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let report = ExampleReport::from(ExampleResult::Success);
+            // act
+            let flag = logger.report_flag(&report);
+            // assert
+            assert_eq!("ok", flag);
+        }
+    }
+
+    mod io_errors {
+        use super::*;
+
+        /// Writer failing on any write after the first `capacity` bytes.
+        struct FailingWriter {
+            capacity: usize,
+        }
+
+        impl io::Write for FailingWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if buf.len() > self.capacity {
+                    return Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+                }
+                self.capacity -= buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn it_records_the_first_error() {
+            // arrange
+            let logger = SerialLogger::new(FailingWriter { capacity: 16 });
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite with a long name");
+            // act
+            logger.enter_suite(&runner, &header);
+            logger.enter_suite(&runner, &header);
+            // assert
+            let error = logger.take_io_error().expect("error to have been recorded");
+            assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+            assert!(logger.take_io_error().is_none());
+        }
+
+        #[test]
+        fn it_surfaces_the_error_on_the_runner() {
+            // arrange
+            let logger = Arc::new(SerialLogger::new(FailingWriter { capacity: 16 }));
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![logger]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+            });
+            // act
+            let result = runner.try_run(&suite);
+            // assert
+            assert!(result.is_ok());
+            let error = runner.last_io_error().expect("error to have been recorded");
+            assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+        }
+    }
+
+    mod seed {
+        use super::*;
+
+        use std::sync::Mutex;
+
+        fn run_shuffled(seed: Option<u64>) -> (Vec<usize>, String) {
+            let logger = Arc::new(SerialLogger::new(vec![]));
+            let mut builder = ConfigurationBuilder::default();
+            builder.parallel(false).shuffle(true);
+            if let Some(seed) = seed {
+                builder.seed(seed);
+            }
+            let runner = Runner::new(builder.build().unwrap(), vec![logger.clone()]);
+            let order = Arc::new(Mutex::new(vec![]));
+            let suite = suite("a suite", order.clone(), |ctx| {
+                for index in 0..10 {
+                    ctx.example("an example", move |order| order.lock().unwrap().push(index));
+                }
+            });
+            let _ = runner.run(&suite);
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            let order = order.lock().unwrap().clone();
+            (order, output)
+        }
+
+        #[test]
+        fn it_reproduces_the_order_with_the_printed_seed() {
+            // arrange
+            let (expected, output) = run_shuffled(None);
+            let seed = output
+                .lines()
+                .find(|line| line.starts_with("Randomized with seed "))
+                .and_then(|line| line["Randomized with seed ".len()..].parse().ok())
+                .expect("seed to have been printed");
+            // act
+            let (order, output) = run_shuffled(Some(seed));
+            // assert
+            assert_eq!(expected, order);
+            assert!(output.contains(&format!("Randomized with seed {}\n", seed)));
+        }
+
+        #[test]
+        fn it_prints_no_seed_without_shuffling() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            // act
+            logger.enter_suite(&runner, &header);
+            // assert
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            assert!(!output.contains("Randomized"));
+        }
+    }
+
+    mod compact {
+        use super::*;
+
+        #[test]
+        fn it_omits_the_prefix_and_blank_lines() {
+            // arrange
+            let logger = Arc::new(SerialLogger::new(vec![]).compact());
+            let configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+            });
+            // act
+            let _ = runner.run(&suite);
+            // assert
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            assert!(output.starts_with("Suite \"a suite\"\n"), "{:?}", output);
+            assert!(!output.contains("tests:"));
+            assert!(!output.contains("\n\n"));
+        }
+    }
+
+    mod empty {
+        use super::*;
+
+        fn run(suite: &Suite<()>) -> String {
+            let logger = Arc::new(SerialLogger::new(vec![]));
+            let configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .error_on_empty(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger.clone()]);
+            let _ = runner.run(suite);
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            output
+        }
+
+        #[test]
+        fn it_warns_about_contexts_without_examples() {
+            // arrange
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+                ctx.context("an empty context", |_| {});
+            });
+            // act
+            let output = run(&suite);
+            // assert
+            assert!(output.contains("warning: Context \"an empty context\" contains no examples"));
+            assert!(output.contains("test result: ok."));
+        }
+
+        #[test]
+        fn it_explains_failing_empty_suites() {
+            // arrange
+            let suite = suite("a suite", (), |_| {});
+            // act
+            let output = run(&suite);
+            // assert
+            assert!(output.contains("error: Suite \"a suite\" contains no examples"));
+            assert!(output.contains("test result: FAILED."));
+        }
+    }
+
+    mod layout {
+        use super::*;
+
+        fn run(layout: Layout) -> String {
+            let logger = Arc::new(SerialLogger::new(vec![]).compact());
+            let configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .output_layout(layout)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("first", |_| {});
+                ctx.context("a context", |ctx| {
+                    ctx.scope(|ctx| {
+                        ctx.example("second", |_| {});
+                    });
+                });
+            });
+            let _ = runner.run(&suite);
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            output
+        }
+
+        #[test]
+        fn it_writes_a_line_per_example_with_its_path() {
+            // arrange
+            let layout = Layout::Flat;
+            // act
+            let output = run(layout);
+            // assert
+            let expected = "a suite > first ... ok
+a suite > a context > second ... ok
+";
+            assert!(output.starts_with(expected), "{}", output);
+            assert!(output.lines().all(|line| !line.starts_with(' ')));
+        }
+
+        #[test]
+        fn it_writes_a_tree_by_default() {
+            // arrange
+            let layout = Layout::default();
+            // act
+            let output = run(layout);
+            // assert
+            let expected = "Suite \"a suite\"
+  Example \"first\" ... ok
+  Context \"a context\"
+    Example \"second\" ... ok
+";
+            assert!(output.starts_with(expected), "{}", output);
+        }
+    }
+
+    mod timestamps {
+        use super::*;
+
+        fn run(logger: SerialLogger<Vec<u8>>) -> String {
+            let logger = Arc::new(logger);
+            let configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("a context", |ctx| {
+                    ctx.example("an example", |_| {});
+                });
+            });
+            let _ = runner.run(&suite);
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            output
+        }
+
+        /// Whether the line starts with a timestamp like `[      42ms] `.
+        fn is_timestamped(line: &str) -> bool {
+            line.len() >= 13
+                && line.starts_with('[')
+                && line[1..9].trim_start().chars().all(|c| c.is_ascii_digit())
+                && !line[1..9].trim_start().is_empty()
+                && &line[9..13] == "ms] "
+        }
+
+        #[test]
+        fn it_prefixes_each_line_with_a_timestamp() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_timestamps();
+            // act
+            let output = run(logger);
+            // assert
+            assert!(output.lines().count() > 3);
+            for line in output.lines() {
+                assert!(is_timestamped(line), "{:?}", line);
+            }
+            let example = output
+                .lines()
+                .find(|line| line.contains("an example"))
+                .unwrap();
+            assert!(example.ends_with("Example \"an example\" ... ok"));
+            assert_eq!(1, example.matches("ms] ").count());
+        }
+
+        #[test]
+        fn it_writes_no_timestamps_by_default() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            // act
+            let output = run(logger);
+            // assert
+            assert!(output.lines().all(|line| !is_timestamped(line)));
+        }
+    }
+
+    mod theme {
+        use super::*;
+
+        #[test]
+        fn it_words_the_output_after_the_theme() {
+            // arrange
+            let theme = Theme {
+                success: "bien".to_owned(),
+                failure: "ÉCHEC".to_owned(),
+                ignored: "ignoré".to_owned(),
+                summary_prefix: "résultat".to_owned(),
+                failures_header: "échecs".to_owned(),
+            };
+            let logger = Arc::new(SerialLogger::new(vec![]).with_theme(theme));
+            let configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(configuration, vec![logger.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("passes", |_| true);
+                ctx.example("fails", |_| false);
+                ctx.xit("is ignored", "later", |_| {});
+            });
+            // act
+            let _ = runner.run(&suite);
+            // assert
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            assert!(
+                output.contains("Example \"passes\" ... bien\n"),
+                "{}",
+                output
+            );
+            assert!(
+                output.contains("Example \"fails\" ... ÉCHEC\n"),
+                "{}",
+                output
+            );
+            assert!(
+                output.contains("It \"is ignored\" ... ignoré"),
+                "{}",
+                output
+            );
+            assert!(output.contains("\néchecs:\n"), "{}", output);
+            assert!(output.contains("résultat: ÉCHEC."), "{}", output);
+            assert!(!output.contains("test result"), "{}", output);
+        }
+    }
+
+    #[cfg(feature = "color")]
+    // The styled output as written is tested in `tests/header_styles.rs`.
+    mod header_styles {
+        use super::*;
+
+        use colored::Color;
+        use header::{ContextHeader, ContextLabel};
+        use std::env;
+
+        /// Constructs a logger while `NO_COLOR` is set to the given value (or unset).
+        fn with_no_color(value: Option<&str>) -> SerialLogger<Vec<u8>> {
+            // Tests run in parallel, yet share the process' environment:
+            static ENVIRONMENT: Mutex<()> = Mutex::new(());
+            let _guard = ENVIRONMENT
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let previous = env::var_os("NO_COLOR");
+            match value {
+                Some(value) => env::set_var("NO_COLOR", value),
+                None => env::remove_var("NO_COLOR"),
+            }
+            let logger = SerialLogger::new(vec![]);
+            match previous {
+                Some(previous) => env::set_var("NO_COLOR", previous),
+                None => env::remove_var("NO_COLOR"),
+            }
+            logger
+        }
+
+        #[test]
+        fn it_styles_suite_and_context_headers() {
+            // arrange
+            let logger = with_no_color(None);
+            let suite = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            let context = ContextHeader::new(ContextLabel::Context, "a context");
+            // act
+            let suite = logger.suite_header(&suite);
+            let context = logger.context_header(&context);
+            // assert
+            assert!(!suite.is_plain());
+            assert_eq!(Some(Color::Cyan), context.fgcolor);
+        }
+
+        #[test]
+        fn it_leaves_plain_headers_unstyled() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).plain_headers();
+            let suite = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            // act
+            let suite = logger.suite_header(&suite);
+            // assert
+            assert!(suite.is_plain());
+        }
+
+        #[test]
+        fn it_respects_no_color() {
+            // arrange
+            let logger = with_no_color(Some("1"));
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            let report = SuiteReport::empty(header.clone());
+            // act
+            let suite = logger.suite_header(&header);
+            let flag = logger.report_flag(&ExampleReport::default());
+            logger.enter_suite(&runner, &header);
+            logger.exit_suite(&runner, &header, &report);
+            // assert
+            assert!(suite.is_plain());
+            assert!(flag.is_plain());
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            assert!(!output.contains('\u{1b}'), "{:?}", output);
+        }
+
+        #[test]
+        fn it_ignores_an_empty_no_color() {
+            // arrange
+            let logger = with_no_color(Some(""));
+            // act
+            let flag = logger.report_flag(&ExampleReport::default());
+            // assert
+            assert_eq!(Some(Color::Green), flag.fgcolor);
+        }
+
+        #[test]
+        fn it_never_styles_if_told_so() {
+            // arrange
+            let logger = with_no_color(None).with_color(ColorChoice::Never);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            // act
+            let suite = logger.suite_header(&header);
+            let flag = logger.report_flag(&ExampleReport::default());
+            // assert
+            assert!(suite.is_plain());
+            assert!(flag.is_plain());
+        }
+    }
+
+    mod report_flag {
+        use super::*;
+
+        #[test]
+        fn it_flags_ignored_examples() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let report = ExampleReport::from(ExampleResult::Ignored(None));
+            // act
+            let flag = logger.report_flag(&report);
+            // assert
+            assert!(flag.to_string().contains("ignored"));
+        }
+    }
+
+    mod write_suite_failures {
+        use super::*;
+
+        #[test]
+        fn it_omits_name_less_scopes() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let line = line!() + 4;
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("a context", |ctx| {
+                    ctx.scope(|ctx| {
+                        ctx.it("fails", |_| {
+                            ExampleResult::failure(Some("reason".to_owned()))
+                        });
+                    });
+                    ctx.it("passes", |_| {});
+                });
+            });
+            let report = runner.try_run(&suite).unwrap_err();
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_suite_failures(&mut buffer, 0, &report)
+                .unwrap();
+            // assert
+            let expected = format!(
+                "
+failures:
+
+Suite \"a suite\"
+  Context \"a context\"
+    It \"fails\" ({}:{})
+      reason
+",
+                file!(),
+                line
+            );
+            assert_eq!(expected, String::from_utf8(buffer).unwrap());
+            assert_eq!(vec![vec!["a context", "fails"]], report.failed_examples());
+        }
+
+        #[test]
+        fn it_lists_failures_in_declaration_order() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                for &context in &["first", "second", "third"] {
+                    ctx.context(context, |ctx| {
+                        for &example in &["a", "b", "c"] {
+                            ctx.it(example, |_| ExampleResult::failure(None));
+                        }
+                    });
+                }
+            });
+            let failures = |shuffle: bool| {
+                let config = ConfigurationBuilder::default()
+                    .parallel(true)
+                    .shuffle(shuffle)
+                    .exit_on_failure(false)
+                    .build()
+                    .unwrap();
+                let report = Runner::new(config, vec![]).try_run(&suite).unwrap_err();
+                let mut buffer = vec![];
+                logger
+                    .write_suite_failures(&mut buffer, 0, &report)
+                    .unwrap();
+                String::from_utf8(buffer).unwrap()
+            };
+            let expected = failures(false);
+            // act
+            let listings: Vec<_> = (0..10).map(|run| failures(run % 2 == 0)).collect();
+            // assert
+            let positions: Vec<_> = ["first", "second", "third"]
+                .iter()
+                .map(|name| expected.find(name).unwrap())
+                .collect();
+            assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+            for listing in listings {
+                assert_eq!(expected, listing);
+            }
+        }
+    }
+
+    mod write_failing_contexts {
+        use super::*;
+
+        #[test]
+        fn it_lists_failing_contexts_with_counts() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("first", |ctx| {
+                    ctx.it("fails", |_| false);
+                    ctx.it("passes", |_| true);
+                    ctx.context("nested", |ctx| {
+                        ctx.it("fails", |_| false);
+                    });
+                });
+                ctx.context("second", |ctx| {
+                    ctx.scope(|ctx| {
+                        ctx.it("fails", |_| false);
+                    });
+                    ctx.it("fails too", |_| false);
+                    ctx.it("passes", |_| true);
+                });
+                ctx.context("third", |ctx| {
+                    ctx.it("passes", |_| true);
+                });
+            });
+            let report = runner.try_run(&suite).unwrap_err();
+            let mut buffer = vec![];
+            // act
+            logger.write_failing_contexts(&mut buffer, &report).unwrap();
+            // assert
+            let expected = "
+failing contexts:
+
+  first: 2 of 3 failed
+  first > nested: 1 of 1 failed
+  second: 2 of 3 failed
+";
+            assert_eq!(expected, String::from_utf8(buffer).unwrap());
+        }
+    }
+
+    mod write_grouped_failures {
+        use super::*;
+
+        #[test]
+        fn it_groups_identical_failure_messages() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_grouped_failures();
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("a context", |ctx| {
+                    for index in 0..3 {
+                        ctx.it(format!("case {}", index), |_| {
+                            ExampleResult::failure(Some("connection refused".to_owned()))
+                        });
+                    }
+                    ctx.it("passes", |_| true);
+                });
+                ctx.it("fails otherwise", |_| {
+                    ExampleResult::failure(Some("timed out".to_owned()))
+                });
+            });
+            let report = runner.try_run(&suite).unwrap_err();
+            let mut buffer = vec![];
+            // act
+            logger.write_grouped_failures(&mut buffer, &report).unwrap();
+            // assert
+            let expected = "
+grouped failures:
+
+  3 examples failed with: connection refused
+    a context > case 0
+    a context > case 1
+    a context > case 2
+  1 example failed with: timed out
+    fails otherwise
+";
+            assert_eq!(expected, String::from_utf8(buffer).unwrap());
+        }
+
+        #[test]
+        fn it_writes_no_groups_by_default() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let header = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+            let report = SuiteReport::new(
+                header,
+                ContextReport::from_blocks(vec![BlockReport::Example(
+                    ExampleHeader::new(ExampleLabel::It, "fails"),
+                    ExampleReport::from(ExampleResult::failure(None)),
+                )]),
+            );
+            let mut buffer = vec![];
+            // act
+            logger.write_suite_suffix(&mut buffer, &report).unwrap();
+            // assert
+            let output = String::from_utf8(buffer).unwrap();
+            assert!(output.contains("test result: FAILED."), "{}", output);
+            assert!(!output.contains("grouped failures"), "{}", output);
+        }
+    }
+
+    mod failure_message_filter {
+        use super::*;
+
+        #[test]
+        fn it_logs_the_filtered_message() {
+            // arrange
+            let logger = Arc::new(SerialLogger::new(vec![]));
+            let filter = |message: &str| message.replace("/tmp/rspec-1234", "<redacted>");
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .failure_message_filter(Arc::new(filter))
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![logger.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("fails", |_| {
+                    ExampleResult::failure(Some("no such file: /tmp/rspec-1234/data".to_owned()))
+                });
+            });
+            // act
+            let _ = runner.run(&suite);
+            // assert
+            let output = String::from_utf8(logger.lock_state().buffer.clone()).unwrap();
+            assert!(output.contains("no such file: <redacted>/data"));
+            assert!(!output.contains("/tmp/rspec-1234"));
+        }
+    }
+
+    mod attempts_note {
+        use super::*;
+
+        #[test]
+        fn it_notes_retries() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let failure = ExampleResult::failure(None);
+            let report = ExampleReport::from(ExampleResult::Success)
+                .with_failed_attempts(vec![failure.clone(), failure]);
+            // act
+            let note = logger.attempts_note(&report);
+            // assert
+            assert_eq!(" (passed after 2 retries)", note);
+        }
+
+        #[test]
+        fn it_notes_nothing_without_retries() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let report = ExampleReport::from(ExampleResult::Success);
+            // act
+            let note = logger.attempts_note(&report);
+            // assert
+            assert_eq!("", note);
+        }
+    }
+
+    mod ignored_note {
+        use super::*;
+
+        #[test]
+        fn it_notes_the_reason() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let report = ExampleReport::from(ExampleResult::Ignored(Some("flaky".to_owned())));
+            // act
+            let note = logger.ignored_note(&report);
+            // assert
+            assert_eq!(" (flaky)", note);
+        }
+    }
+
+    mod write_example_failure {
+        use super::*;
+
+        #[test]
+        fn it_writes_a_diff_of_expected_and_actual() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let result = ExampleResult::mismatch(
+                Some("mismatch".to_owned()),
+                "first\nsecond\nthird".to_owned(),
+                "first\nchanged\nthird".to_owned(),
+            );
+            let report = ExampleReport::from(result);
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            let output = String::from_utf8(buffer).unwrap();
+            assert!(output.contains("  mismatch\n"));
+            assert!(output.contains("    first\n"));
+            assert!(output.contains("- second"));
+            assert!(output.contains("+ changed"));
+            assert!(output.contains("    third\n"));
+        }
+
+        #[test]
+        fn it_wraps_long_messages_at_the_given_width() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_wrapping(24);
+            let message = "expected the response to contain a header named x-request-id";
+            let report = ExampleReport::from(ExampleResult::failure(Some(message.to_owned())));
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 2, &report)
+                .unwrap();
+            // assert
+            let expected = "    expected the
+    response to contain
+    a header named
+    x-request-id
+";
+            let output = String::from_utf8(buffer).unwrap();
+            assert_eq!(expected, output);
+            assert!(output.lines().all(|line| line.len() <= 24));
+        }
+
+        #[test]
+        fn it_keeps_words_exceeding_the_width_whole() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_wrapping(10);
+            let message = "no such file: /tmp/rspec/fixtures\nsee above";
+            let report = ExampleReport::from(ExampleResult::failure(Some(message.to_owned())));
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            let expected = "  no such
+  file:
+  /tmp/rspec/fixtures
+  see
+  above
+";
+            assert_eq!(expected, String::from_utf8(buffer).unwrap());
+        }
+
+        #[test]
+        fn it_keeps_the_indentation_and_spacing_of_lines() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_wrapping(24);
+            let message =
+                "mismatched fields:\n    name:   \"a\" != \"b\"\n    flags:  none of them set";
+            let report = ExampleReport::from(ExampleResult::failure(Some(message.to_owned())));
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            let expected = "  mismatched fields:
+      name:   \"a\" != \"b\"
+      flags:  none of
+      them set
+";
+            assert_eq!(expected, String::from_utf8(buffer).unwrap());
+        }
+
+        #[test]
+        fn it_writes_the_plain_message_otherwise() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let result = ExampleResult::failure(Some("reason".to_owned()));
+            let report = ExampleReport::from(result);
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_example_failure(&mut buffer, 1, &report)
+                .unwrap();
+            // assert
+            assert_eq!("  reason\n", String::from_utf8(buffer).unwrap());
+        }
+    }
+
+    mod percentiles {
+        use super::*;
+
+        fn report(millis: &[u64]) -> SuiteReport {
+            let blocks = millis
+                .iter()
+                .map(|&millis| {
+                    let header = ExampleHeader::new(ExampleLabel::Example, "an example");
+                    let report =
+                        ExampleReport::new(ExampleResult::Success, Duration::from_millis(millis));
+                    BlockReport::Example(header, report)
+                })
+                .collect();
+            let context = ContextHeader::new(ContextLabel::Context, "a context");
+            let nested = ContextReport::from_blocks(blocks);
+            SuiteReport::new(
+                SuiteHeader::new(SuiteLabel::Suite, "a suite"),
+                ContextReport::from_blocks(vec![BlockReport::Context(Some(context), nested)]),
+            )
+        }
+
+        #[test]
+        fn it_computes_nearest_rank_percentiles() {
+            // arrange
+            let durations: Vec<_> = (1..=200).map(Duration::from_millis).collect();
+            // act
+            let percentiles: Vec<_> = [50, 90, 99, 100]
+                .iter()
+                .map(|&percent| percentile(&durations, percent))
+                .collect();
+            // assert
+            let expected: Vec<_> = [100, 180, 198, 200]
+                .iter()
+                .map(|&millis| Duration::from_millis(millis))
+                .collect();
+            assert_eq!(expected, percentiles);
+            assert_eq!(durations[0], percentile(&durations[..1], 99));
+        }
+
+        #[test]
+        fn it_writes_percentiles_of_nested_examples() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_percentiles();
+            let report = report(&[40, 10, 30, 20, 50, 60, 70, 80, 90, 1_000]);
+            let mut buffer = vec![];
+            // act
+            logger.write_suite_suffix(&mut buffer, &report).unwrap();
+            // assert
+            let output = String::from_utf8(buffer).unwrap();
+            assert!(
+                output.contains("\npercentiles: p50 0.050s, p90 0.090s, p99 1.000s.\n"),
+                "{}",
+                output
+            );
+        }
+
+        #[test]
+        fn it_writes_percentiles_of_examples_which_ran_only() {
+            // arrange
+            let logger = SerialLogger::new(vec![]).with_percentiles();
+            let header = ExampleHeader::new(ExampleLabel::Example, "an example");
+            let blocks = vec![
+                (ExampleResult::Success, 10),
+                (ExampleResult::failure(None), 20),
+                (ExampleResult::Ignored(None), 1_000),
+            ];
+            let blocks = blocks
+                .into_iter()
+                .map(|(result, millis)| {
+                    let report = ExampleReport::new(result, Duration::from_millis(millis));
+                    BlockReport::Example(header.clone(), report)
+                })
+                .collect();
+            let report = SuiteReport::new(
+                SuiteHeader::new(SuiteLabel::Suite, "a suite"),
+                ContextReport::from_blocks(blocks),
+            );
+            let mut buffer = vec![];
+            // act
+            logger.write_suite_suffix(&mut buffer, &report).unwrap();
+            // assert
+            let output = String::from_utf8(buffer).unwrap();
+            assert!(
+                output.contains("\npercentiles: p50 0.010s, p90 0.020s, p99 0.020s.\n"),
+                "{}",
+                output
+            );
+        }
+
+        #[test]
+        fn it_writes_no_percentiles_by_default() {
+            // arrange
+            let logger = SerialLogger::new(vec![]);
+            let mut buffer = vec![];
+            // act
+            logger
+                .write_suite_suffix(&mut buffer, &report(&[10]))
+                .unwrap();
+            // assert
+            assert!(!String::from_utf8(buffer).unwrap().contains("percentiles"));
+        }
+    }
+
+    mod format_duration {
+        use super::*;
+
+        fn subject(duration: Duration) -> String {
+            SerialLogger::<Vec<u8>>::format_duration(duration)
+        }
+
+        #[test]
+        fn it_formats_seconds() {
+            assert_eq!("1.234s", subject(Duration::from_millis(1_234)));
+        }
+
+        #[test]
+        fn it_formats_minutes() {
+            assert_eq!("2m 3.004s", subject(Duration::from_millis(123_004)));
+        }
+
+        #[test]
+        fn it_formats_hours() {
+            assert_eq!("1h 0m 1.000s", subject(Duration::from_secs(3_601)));
+        }
     }
 }
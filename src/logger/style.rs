@@ -0,0 +1,71 @@
+//! Console styling, degrading to plain text when the `color` feature is disabled.
+
+use std::env;
+
+#[cfg(feature = "color")]
+pub(crate) use colored::{ColoredString as StyledString, Colorize};
+
+#[cfg(not(feature = "color"))]
+pub(crate) type StyledString = String;
+
+/// No-op stand-in for `colored::Colorize`.
+#[cfg(not(feature = "color"))]
+pub(crate) trait Colorize: Sized + Into<String> {
+    fn red(self) -> StyledString {
+        self.into()
+    }
+
+    fn green(self) -> StyledString {
+        self.into()
+    }
+
+    fn yellow(self) -> StyledString {
+        self.into()
+    }
+
+    fn cyan(self) -> StyledString {
+        self.into()
+    }
+
+    fn normal(self) -> StyledString {
+        self.into()
+    }
+
+    fn bold(self) -> StyledString {
+        self.into()
+    }
+
+    fn clear(self) -> StyledString {
+        self.into()
+    }
+}
+
+#[cfg(not(feature = "color"))]
+impl Colorize for &str {}
+
+#[cfg(not(feature = "color"))]
+impl Colorize for String {}
+
+/// Whether loggers style their output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorChoice {
+    /// Styles the output, unless the `NO_COLOR` environment variable is set
+    /// (to a non-empty value, see [no-color.org](https://no-color.org)).
+    #[default]
+    Auto,
+    /// Never styles the output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to style the output, as of now.
+    pub(crate) fn resolve(self) -> bool {
+        match self {
+            ColorChoice::Auto => match env::var_os("NO_COLOR") {
+                Some(value) => value.is_empty(),
+                None => true,
+            },
+            ColorChoice::Never => false,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// The wording of a logger's output, for localizing or rebranding it.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use std::io;
+/// #
+/// # pub fn main() {
+/// let theme = rspec::Theme {
+///     success: "bien".to_owned(),
+///     failure: "ÉCHEC".to_owned(),
+///     ..rspec::Theme::default()
+/// };
+/// let logger = rspec::Logger::new(io::stdout()).with_theme(theme);
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// Flags passed examples (and suites), `"ok"` by default.
+    pub success: String,
+    /// Flags failed examples (and suites), `"FAILED"` by default.
+    pub failure: String,
+    /// Flags ignored examples, `"ignored"` by default.
+    pub ignored: String,
+    /// Precedes the suite's flag and counts, `"test result"` by default.
+    pub summary_prefix: String,
+    /// Heads the list of failures, `"failures"` by default.
+    pub failures_header: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            success: "ok".to_owned(),
+            failure: "FAILED".to_owned(),
+            ignored: "ignored".to_owned(),
+            summary_prefix: "test result".to_owned(),
+            failures_header: "failures".to_owned(),
+        }
+    }
+}
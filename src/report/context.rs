@@ -1,17 +1,150 @@
-use report::{BlockReport, Report};
-use time::Duration;
+use report::{BlockReport, ExampleReport, ExampleResult, Failure, Report};
+use std::time::Duration;
 
 /// `ContextReport` holds the results of a context's test execution.
-#[derive(PartialEq, Eq, Clone, Debug, new)]
+#[must_use]
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct ContextReport {
     sub_reports: Vec<BlockReport>,
-    duration: Duration,
+    wall_duration: Duration,
+    cpu_duration: Duration,
+    /// Whether the runner entered the context, rather than skipping it as a whole.
+    entered: bool,
 }
 
 impl ContextReport {
+    pub fn new(sub_reports: Vec<BlockReport>, wall_duration: Duration) -> Self {
+        let cpu_duration = sub_reports.iter().fold(Duration::default(), |sum, report| {
+            sum + report.get_cpu_duration()
+        });
+        ContextReport {
+            sub_reports,
+            wall_duration,
+            cpu_duration,
+            entered: true,
+        }
+    }
+
+    /// A report of the given blocks of a context the runner skipped as a whole
+    /// (e.g. as it was filtered out), rather than entering it.
+    pub(crate) fn skipped(sub_reports: Vec<BlockReport>) -> Self {
+        ContextReport {
+            entered: false,
+            ..ContextReport::from_blocks(sub_reports)
+        }
+    }
+
+    /// A report of a context without any blocks.
+    pub fn empty() -> Self {
+        ContextReport::new(vec![], Duration::default())
+    }
+
+    /// A report of the given blocks, with a wall duration of zero.
+    pub fn from_blocks(sub_reports: Vec<BlockReport>) -> Self {
+        ContextReport::new(sub_reports, Duration::default())
+    }
+
     pub fn get_blocks(&self) -> &[BlockReport] {
         &self.sub_reports[..]
     }
+
+    /// The real time elapsed while evaluating the context.
+    pub fn get_wall_duration(&self) -> Duration {
+        self.wall_duration
+    }
+
+    /// The summed up durations of the context's examples.
+    ///
+    /// For contexts evaluated in parallel this is usually larger than the wall duration.
+    pub fn get_cpu_duration(&self) -> Duration {
+        self.cpu_duration
+    }
+
+    /// Whether the runner skipped the context as a whole (e.g. as it was filtered out),
+    /// as opposed to a context it entered, even if all of its examples ended up ignored.
+    pub fn is_skipped(&self) -> bool {
+        !self.entered
+    }
+
+    /// The report with all of its (and its blocks') durations zeroed.
+    pub fn without_durations(&self) -> ContextReport {
+        let sub_reports = self
+            .sub_reports
+            .iter()
+            .map(BlockReport::without_durations)
+            .collect();
+        ContextReport {
+            entered: self.entered,
+            ..ContextReport::from_blocks(sub_reports)
+        }
+    }
+
+    /// The report of the first example with the given path, if any.
+    pub(crate) fn find_example(&self, path: &[&str]) -> Option<&ExampleReport> {
+        self.sub_reports.iter().find_map(|block| match block {
+            BlockReport::Context(Some(ref header), ref report) => match path.split_first() {
+                Some((name, rest)) if *name == header.name => report.find_example(rest),
+                _ => None,
+            },
+            BlockReport::Context(None, ref report) => report.find_example(path),
+            BlockReport::Example(ref header, ref report) => {
+                if path.len() == 1 && path[0] == header.name {
+                    Some(report)
+                } else {
+                    None
+                }
+            }
+        })
+    }
+
+    /// Collects the durations of the examples which ran (i.e. passed or failed).
+    pub(crate) fn collect_example_durations(&self, durations: &mut Vec<Duration>) {
+        for block in self.sub_reports.iter() {
+            match block {
+                BlockReport::Context(_, ref report) => report.collect_example_durations(durations),
+                BlockReport::Example(_, ref report) => {
+                    if report.is_success() || report.is_failure() {
+                        durations.push(report.get_duration())
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn collect_failures(&self, path: &mut Vec<String>, failures: &mut Vec<Failure>) {
+        for block in self.sub_reports.iter().filter(|block| block.is_failure()) {
+            match block {
+                BlockReport::Context(ref header, ref report) => {
+                    if let Some(header) = header.as_ref() {
+                        path.push(header.name.to_string());
+                    }
+                    report.collect_failures(path, failures);
+                    if header.is_some() {
+                        path.pop();
+                    }
+                }
+                BlockReport::Example(ref header, ref report) => {
+                    let mut example_path = path.clone();
+                    example_path.push(header.name.to_string());
+                    let message = match *report.get_result() {
+                        ExampleResult::Failure { ref message, .. } => message.clone(),
+                        _ => None,
+                    };
+                    failures.push(Failure {
+                        path: example_path,
+                        message,
+                        duration: report.get_duration(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Default for ContextReport {
+    fn default() -> Self {
+        ContextReport::empty()
+    }
 }
 
 impl Report for ContextReport {
@@ -46,11 +179,195 @@ impl Report for ContextReport {
     }
 
     fn get_duration(&self) -> Duration {
-        self.duration
+        self.wall_duration
+    }
+
+    fn get_cpu_duration(&self) -> Duration {
+        self.cpu_duration
     }
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
+
+    use header::{ContextHeader, ContextLabel, ExampleHeader, ExampleLabel};
+    use report::ExampleResult;
+
+    fn example(result: ExampleResult) -> BlockReport {
+        let header = ExampleHeader::new(ExampleLabel::It, "example");
+        BlockReport::Example(header, ExampleReport::from(result))
+    }
+
+    #[test]
+    fn get_total() {
+        // arrange
+        let header = ContextHeader::new(ContextLabel::Context, "context");
+        let nested = ContextReport::new(
+            vec![
+                example(ExampleResult::Ignored(None)),
+                example(ExampleResult::Success),
+            ],
+            Duration::default(),
+        );
+        // act
+        let report = ContextReport::new(
+            vec![
+                example(ExampleResult::Success),
+                example(ExampleResult::failure(None)),
+                BlockReport::Context(Some(header), nested),
+            ],
+            Duration::default(),
+        );
+        // assert
+        assert_eq!(4, report.get_total());
+        assert_eq!(2, report.get_passed());
+    }
+
+    mod success_rate {
+        use super::*;
+
+        #[test]
+        fn it_is_one_if_all_passed() {
+            let report = ContextReport::from_blocks(vec![
+                example(ExampleResult::Success),
+                example(ExampleResult::Success),
+            ]);
+            assert_eq!(1.0, report.success_rate());
+        }
+
+        #[test]
+        fn it_is_zero_if_all_failed() {
+            let report = ContextReport::from_blocks(vec![
+                example(ExampleResult::failure(None)),
+                example(ExampleResult::failure(None)),
+            ]);
+            assert_eq!(0.0, report.success_rate());
+        }
+
+        #[test]
+        fn it_is_the_share_of_passed_examples() {
+            let report = ContextReport::from_blocks(vec![
+                example(ExampleResult::Success),
+                example(ExampleResult::Success),
+                example(ExampleResult::Ignored(None)),
+                example(ExampleResult::failure(None)),
+            ]);
+            assert_eq!(0.5, report.success_rate());
+        }
+
+        #[test]
+        fn it_is_zero_without_examples() {
+            assert_eq!(0.0, ContextReport::empty().success_rate());
+        }
+    }
+
+    mod is_skipped {
+        use super::*;
+
+        use block::suite;
+        use runner::{ConfigurationBuilder, Runner};
+        use std::sync::Arc;
+
+        #[test]
+        fn it_marks_filtered_out_contexts() {
+            // arrange
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("kept", |ctx| {
+                    ctx.example("an example", |_| true);
+                });
+                ctx.context("dropped", |ctx| {
+                    ctx.example("an example", |_| true);
+                });
+            });
+            let filter = |path: &[&str]| path.first() == Some(&"kept");
+            let configuration = ConfigurationBuilder::default()
+                .filter(Arc::new(filter))
+                .build()
+                .unwrap();
+            // act
+            let report = Runner::new(configuration, vec![]).run(&suite);
+            // assert
+            let skipped: Vec<_> = report
+                .get_context()
+                .get_blocks()
+                .iter()
+                .map(|block| match block {
+                    BlockReport::Context(_, ref report) => report.is_skipped(),
+                    _ => panic!("expected a context"),
+                })
+                .collect();
+            assert_eq!(vec![false, true], skipped);
+            assert!(!report.get_context().is_skipped());
+        }
+
+        #[test]
+        fn it_does_not_mark_entered_contexts_with_only_ignored_examples() {
+            // arrange
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("ignoring", |ctx| {
+                    ctx.example("an example", |_| ExampleResult::Ignored(None));
+                });
+            });
+            // act
+            let report = Runner::new(Default::default(), vec![]).run(&suite);
+            // assert
+            match report.get_context().get_blocks() {
+                [BlockReport::Context(_, ref report)] => {
+                    assert_eq!(1, report.get_ignored());
+                    assert!(!report.is_skipped());
+                    assert!(!report.without_durations().is_skipped());
+                }
+                blocks => panic!("expected a single context, got {:?}", blocks),
+            }
+        }
+
+        #[test]
+        fn it_does_not_mark_contexts_with_failures() {
+            let report = ContextReport::from_blocks(vec![
+                example(ExampleResult::Ignored(None)),
+                example(ExampleResult::failure(None)),
+            ]);
+            assert!(!report.is_skipped());
+        }
+
+        #[test]
+        fn it_does_not_mark_empty_contexts() {
+            assert!(!ContextReport::empty().is_skipped());
+        }
+    }
+
+    #[test]
+    fn empty() {
+        // act
+        let report = ContextReport::empty();
+        // assert
+        assert_eq!(ContextReport::default(), report);
+        assert!(report.get_blocks().is_empty());
+        assert!(report.is_success());
+        assert!(!report.is_failure());
+        assert_eq!(0, report.get_total());
+        assert_eq!(Duration::default(), report.get_duration());
+    }
+
+    #[test]
+    fn from_blocks() {
+        // arrange
+        let duration = Duration::from_millis(5);
+        let blocks = vec![
+            BlockReport::Example(
+                ExampleHeader::new(ExampleLabel::It, "example"),
+                ExampleReport::new(ExampleResult::Success, duration),
+            ),
+            example(ExampleResult::Ignored(None)),
+        ];
+        // act
+        let report = ContextReport::from_blocks(blocks);
+        // assert
+        assert_eq!(1, report.get_passed());
+        assert_eq!(0, report.get_failed());
+        assert_eq!(1, report.get_ignored());
+        assert_eq!(Duration::default(), report.get_wall_duration());
+        assert_eq!(duration, report.get_cpu_duration());
+    }
 }
@@ -1,6 +1,6 @@
 use std::convert::From;
 
-use time::Duration;
+use std::time::Duration;
 
 use report::Report;
 
@@ -10,17 +10,106 @@ use expectest::core::TestResult as ExpectestResult;
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ExampleResult {
     Success,
-    Failure(Option<String>),
-    Ignored,
+    Failure {
+        message: Option<String>,
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    /// An example which wasn't run, with the reason why (if any).
+    Ignored(Option<String>),
 }
 
 impl ExampleResult {
-    fn is_success(&self) -> bool {
+    /// Creates a failure with an optional message, but no expected/actual values.
+    pub fn failure(message: Option<String>) -> Self {
+        ExampleResult::Failure {
+            message,
+            expected: None,
+            actual: None,
+        }
+    }
+
+    /// Creates a failure for a mismatch between an expected and an actual value.
+    pub fn mismatch(message: Option<String>, expected: String, actual: String) -> Self {
+        ExampleResult::Failure {
+            message,
+            expected: Some(expected),
+            actual: Some(actual),
+        }
+    }
+
+    /// Applies `filter` to the failure's message (if any).
+    pub(crate) fn map_message<F>(self, filter: F) -> Self
+    where
+        F: FnOnce(&str) -> String,
+    {
+        match self {
+            ExampleResult::Failure {
+                message: Some(message),
+                expected,
+                actual,
+            } => ExampleResult::Failure {
+                message: Some(filter(&message)),
+                expected,
+                actual,
+            },
+            result => result,
+        }
+    }
+
+    /// Appends the given note to a failure's message (on a line of its own).
+    pub(crate) fn with_note(self, note: &str) -> Self {
+        match self {
+            ExampleResult::Failure {
+                message,
+                expected,
+                actual,
+            } => ExampleResult::Failure {
+                message: Some(match message {
+                    Some(message) => format!("{}\n{}", message, note),
+                    None => note.to_owned(),
+                }),
+                expected,
+                actual,
+            },
+            result => result,
+        }
+    }
+
+    /// Evaluates `check` only if the result is a success, otherwise returning the result itself,
+    /// for chaining an example's checks without panicking on the first failing one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rspec;
+    /// #
+    /// # pub fn main() {
+    /// rspec::run(&rspec::describe("a vector", vec![1, 2, 3], |ctx| {
+    ///     ctx.it("holds the expected values", |vec| {
+    ///         rspec::expect_eq(3, vec.len())
+    ///             .and_then(|| rspec::expect_eq(Some(&1), vec.first()))
+    ///             .and_then(|| rspec::expect_eq(Some(&3), vec.last()))
+    ///     });
+    /// }));
+    /// # }
+    /// ```
+    pub fn and_then<F>(self, check: F) -> Self
+    where
+        F: FnOnce() -> ExampleResult,
+    {
+        match self {
+            ExampleResult::Success => check(),
+            result => result,
+        }
+    }
+
+    pub(crate) fn is_success(&self) -> bool {
         &ExampleResult::Success == self
     }
 
-    fn is_failure(&self) -> bool {
-        matches!(self, &ExampleResult::Failure(_))
+    pub(crate) fn is_failure(&self) -> bool {
+        matches!(self, &ExampleResult::Failure { .. })
     }
 
     fn get_passed(&self) -> u32 {
@@ -32,7 +121,7 @@ impl ExampleResult {
     }
 
     fn get_failed(&self) -> u32 {
-        if let ExampleResult::Failure(_) = self {
+        if let ExampleResult::Failure { .. } = self {
             1
         } else {
             0
@@ -40,7 +129,7 @@ impl ExampleResult {
     }
 
     fn get_ignored(&self) -> u32 {
-        if &ExampleResult::Ignored == self {
+        if let ExampleResult::Ignored(_) = self {
             1
         } else {
             0
@@ -48,6 +137,53 @@ impl ExampleResult {
     }
 }
 
+/// Conversion of the values returned by examples into their results.
+///
+/// This is the single extension point for plugging custom result types into rspec:
+/// it is implemented for every type convertible `Into<ExampleResult>`
+/// (i.e. `()`, `bool`, `Result<T, E: Display>`, `Option<T>`, …), and can be implemented
+/// for one's own types directly.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::report::{ExampleResult, IntoExampleResult};
+/// #
+/// enum Verdict {
+///     Pass,
+///     Fail(&'static str),
+/// }
+///
+/// impl IntoExampleResult for Verdict {
+///     fn into_example_result(self) -> ExampleResult {
+///         match self {
+///             Verdict::Pass => ExampleResult::Success,
+///             Verdict::Fail(reason) => ExampleResult::failure(Some(reason.to_owned())),
+///         }
+///     }
+/// }
+///
+/// # pub fn main() {
+/// rspec::run(&rspec::suite("a test suite", (), |ctx| {
+///     ctx.it("passes", |_env| Verdict::Pass);
+/// }));
+/// # }
+/// ```
+pub trait IntoExampleResult {
+    fn into_example_result(self) -> ExampleResult;
+}
+
+impl<T> IntoExampleResult for T
+where
+    T: Into<ExampleResult>,
+{
+    fn into_example_result(self) -> ExampleResult {
+        self.into()
+    }
+}
+
 /// rspec considers examples returning `()` a success.
 impl From<()> for ExampleResult {
     fn from(_other: ()) -> ExampleResult {
@@ -61,26 +197,71 @@ impl From<bool> for ExampleResult {
         if other {
             ExampleResult::Success
         } else {
-            ExampleResult::Failure(Some(
+            ExampleResult::failure(Some(
                 "assertion failed: `expected condition to be true`".to_owned(),
             ))
         }
     }
 }
 
-/// rspec considers examples returning `Result::Ok(…)` a success, `Result::Err(…)` a failure.
+/// rspec considers examples returning `Result::Ok(…)` a success, `Result::Err(…)` a failure,
+/// the error's `Display` representation becoming the failure's message.
 impl<T1, T2> From<Result<T1, T2>> for ExampleResult
 where
-    T2: ::std::fmt::Debug,
+    T2: ::std::fmt::Display,
 {
     fn from(other: Result<T1, T2>) -> ExampleResult {
         match other {
             Ok(_) => ExampleResult::Success,
-            Err(error) => ExampleResult::Failure(Some(format!("{:?}", error))),
+            Err(error) => ExampleResult::failure(Some(error.to_string())),
         }
     }
 }
 
+/// rspec considers examples returning `Option::Some(…)` a success, `Option::None` a failure.
+impl<T> From<Option<T>> for ExampleResult {
+    fn from(other: Option<T>) -> ExampleResult {
+        match other {
+            Some(_) => ExampleResult::Success,
+            None => ExampleResult::failure(Some(
+                "assertion failed: `expected Some(…), got None`".to_owned(),
+            )),
+        }
+    }
+}
+
+/// The value for an example to return for being skipped (i.e. reported as ignored)
+/// for the given reason, as decided at run time.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::report::{ExampleResult, Skip};
+/// #
+/// # pub fn main() {
+/// rspec::run(&rspec::suite("a test suite", (), |ctx| {
+///     ctx.it("needs the network", |_env| {
+///         if std::env::var_os("OFFLINE").is_some() {
+///             return ExampleResult::from(Skip("offline".to_owned()));
+///         }
+///         // …
+///         ExampleResult::Success
+///     });
+/// }));
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Skip(pub String);
+
+/// rspec considers examples returning `Skip(…)` ignored, for the skip's reason.
+impl From<Skip> for ExampleResult {
+    fn from(other: Skip) -> ExampleResult {
+        ExampleResult::Ignored(Some(other.0))
+    }
+}
+
 /// rspec considers examples returning `ExpectestResult::Ok(…)` a success, `ExpectestResult::Err(…)` a failure.
 #[cfg(feature = "expectest_compat")]
 impl From<ExpectestResult> for ExampleResult {
@@ -88,23 +269,109 @@ impl From<ExpectestResult> for ExampleResult {
         match other {
             ExpectestResult::Success => ExampleResult::Success,
             ExpectestResult::Failure(failure) => {
-                ExampleResult::Failure(Some(format!("{:?}", failure)))
+                ExampleResult::failure(Some(format!("{:?}", failure)))
             }
         }
     }
 }
 
+/// Compares an expected against an actual value, returning a failure carrying
+/// the debug representation of both values if they don't match.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # pub fn main() {
+/// rspec::run(&rspec::describe("addition", (), |ctx| {
+///     ctx.it("adds up", |_| rspec::expect_eq(4, 2 + 2));
+/// }));
+/// # }
+/// ```
+pub fn expect_eq<E, A>(expected: E, actual: A) -> ExampleResult
+where
+    E: PartialEq<A> + ::std::fmt::Debug,
+    A: ::std::fmt::Debug,
+{
+    if expected == actual {
+        ExampleResult::Success
+    } else {
+        ExampleResult::mismatch(
+            Some("assertion failed: `(expected == actual)`".to_owned()),
+            format!("{:#?}", expected),
+            format!("{:#?}", actual),
+        )
+    }
+}
+
 /// `ExampleReport` holds the results of a context example's test execution.
+#[must_use]
 #[derive(Clone, PartialEq, Eq, Debug, new)]
 pub struct ExampleReport {
     result: ExampleResult,
     duration: Duration,
+    /// The results of any failed attempts preceding the final one.
+    #[new(default)]
+    failed_attempts: Vec<ExampleResult>,
+    /// The output (e.g. of panics) captured while evaluating the example, if failed.
+    #[new(default)]
+    captured_output: Option<String>,
 }
 
 impl ExampleReport {
+    pub(crate) fn with_failed_attempts(mut self, failed_attempts: Vec<ExampleResult>) -> Self {
+        self.failed_attempts = failed_attempts;
+        self
+    }
+
+    pub(crate) fn with_captured_output(mut self, captured_output: Option<String>) -> Self {
+        self.captured_output = captured_output;
+        self
+    }
+
+    /// The report with its duration zeroed.
+    pub fn without_durations(&self) -> ExampleReport {
+        ExampleReport {
+            duration: Duration::default(),
+            ..self.clone()
+        }
+    }
+
     pub fn get_result(&self) -> &ExampleResult {
         &self.result
     }
+
+    /// How often the example got evaluated (i.e. `1` if it wasn't retried).
+    pub fn get_attempts(&self) -> u32 {
+        self.failed_attempts.len() as u32 + 1
+    }
+
+    /// The results of the failed attempts preceding the final one (in chronological order).
+    pub fn get_failed_attempts(&self) -> &[ExampleResult] {
+        &self.failed_attempts
+    }
+
+    /// The output of any panics while evaluating the example, if it failed.
+    ///
+    /// Only captured when the runner swallows panics
+    /// (see [`Configuration.swallow_panics`](../struct.Configuration.html#structfield.swallow_panics)).
+    pub fn get_captured_output(&self) -> Option<&str> {
+        self.captured_output.as_deref()
+    }
+}
+
+impl Default for ExampleReport {
+    fn default() -> Self {
+        ExampleReport::from(ExampleResult::Success)
+    }
+}
+
+/// A report of the given result, with a duration of zero.
+impl From<ExampleResult> for ExampleReport {
+    fn from(result: ExampleResult) -> Self {
+        ExampleReport::new(result, Duration::default())
+    }
 }
 
 impl Report for ExampleReport {
@@ -137,6 +404,25 @@ impl Report for ExampleReport {
 mod tests {
     use super::*;
 
+    #[test]
+    fn report_from_result() {
+        // act
+        let report = ExampleReport::from(ExampleResult::failure(None));
+        // assert
+        assert!(report.is_failure());
+        assert_eq!(1, report.get_failed());
+        assert_eq!(1, report.get_total());
+        assert_eq!(1, report.get_attempts());
+        assert_eq!(Duration::default(), report.get_duration());
+    }
+
+    #[test]
+    fn report_default() {
+        let report = ExampleReport::default();
+        assert!(report.is_success());
+        assert_eq!(1, report.get_passed());
+    }
+
     #[test]
     fn from_void() {
         assert!(ExampleResult::from(()).is_success());
@@ -150,10 +436,154 @@ mod tests {
 
     #[test]
     fn from_result() {
-        let ok_result: Result<(), ()> = Ok(());
-        let err_result: Result<(), ()> = Err(());
+        let ok_result: Result<(), String> = Ok(());
+        let err_result: Result<(), String> = Err("no \"answer\"".to_owned());
         assert!(ExampleResult::from(ok_result).is_success());
-        assert!(ExampleResult::from(err_result).is_failure());
+        assert_eq!(
+            ExampleResult::failure(Some("no \"answer\"".to_owned())),
+            ExampleResult::from(err_result)
+        );
+    }
+
+    #[test]
+    fn from_option() {
+        assert!(ExampleResult::from(Some(42)).is_success());
+        assert!(ExampleResult::from(None::<()>).is_failure());
+    }
+
+    #[test]
+    fn from_skip() {
+        let expected = ExampleResult::Ignored(Some("not today".to_owned()));
+        assert_eq!(expected, ExampleResult::from(Skip("not today".to_owned())));
+    }
+
+    mod and_then {
+        use super::*;
+
+        #[test]
+        fn it_chains_successes() {
+            // act
+            let result = ExampleResult::Success
+                .and_then(|| ExampleResult::Success)
+                .and_then(|| expect_eq(2, 1 + 1));
+            // assert
+            assert_eq!(ExampleResult::Success, result);
+        }
+
+        #[test]
+        fn it_propagates_the_first_failure() {
+            // arrange
+            let mut evaluated = vec![];
+            // act
+            let result = ExampleResult::Success
+                .and_then(|| {
+                    evaluated.push(1);
+                    ExampleResult::failure(Some("first".to_owned()))
+                })
+                .and_then(|| {
+                    evaluated.push(2);
+                    ExampleResult::failure(Some("second".to_owned()))
+                });
+            // assert
+            assert_eq!(ExampleResult::failure(Some("first".to_owned())), result);
+            assert_eq!(vec![1], evaluated);
+        }
+
+        #[test]
+        fn it_short_circuits_on_ignored_results() {
+            // act
+            let result = ExampleResult::Ignored(None).and_then(|| panic!("not evaluated"));
+            // assert
+            assert_eq!(ExampleResult::Ignored(None), result);
+        }
+    }
+
+    mod skip {
+        use super::*;
+
+        use block::suite;
+        use runner::Runner;
+
+        #[test]
+        fn it_reports_skipped_examples_as_ignored() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("skips", |_| Skip("not compiled in".to_owned()));
+                ctx.example("passes", |_| true);
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            assert_eq!((1, 1), (report.get_passed(), report.get_ignored()));
+            let skipped = report.get_context().find_example(&["skips"]).unwrap();
+            let expected = ExampleResult::Ignored(Some("not compiled in".to_owned()));
+            assert_eq!(&expected, skipped.get_result());
+        }
+    }
+
+    mod into_example_result {
+        use super::*;
+
+        use block::suite;
+        use runner::Runner;
+
+        /// A custom result type, convertible into an `ExampleResult` only via `IntoExampleResult`.
+        struct Verdict(Option<&'static str>);
+
+        impl IntoExampleResult for Verdict {
+            fn into_example_result(self) -> ExampleResult {
+                match self.0 {
+                    None => ExampleResult::Success,
+                    Some(reason) => ExampleResult::failure(Some(reason.to_owned())),
+                }
+            }
+        }
+
+        #[test]
+        fn it_flows_custom_result_types_through_examples() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("passes", |_| Verdict(None));
+                ctx.example("fails", |_| Verdict(Some("custom failure")));
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            assert_eq!(1, report.get_passed());
+            let failure = report.get_context().find_example(&["fails"]).unwrap();
+            let expected = ExampleResult::failure(Some("custom failure".to_owned()));
+            assert_eq!(&expected, failure.get_result());
+        }
+    }
+
+    #[test]
+    fn expect_eq_success() {
+        assert!(expect_eq(1, 1).is_success());
+    }
+
+    #[test]
+    fn expect_eq_failure() {
+        match expect_eq(1, 2) {
+            ExampleResult::Failure {
+                expected, actual, ..
+            } => {
+                assert_eq!(Some("1".to_owned()), expected);
+                assert_eq!(Some("2".to_owned()), actual);
+            }
+            result => panic!("expected a failure, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn failure_without_values() {
+        let expected = ExampleResult::Failure {
+            message: Some("message".to_owned()),
+            expected: None,
+            actual: None,
+        };
+        assert_eq!(expected, ExampleResult::failure(Some("message".to_owned())));
     }
 
     #[cfg(feature = "expectest_compat")]
@@ -0,0 +1,185 @@
+//! Hierarchical JSON output of reports, as expected by test explorers (e.g. VS Code's).
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use report::{BlockReport, ContextReport, Report, SuiteReport};
+
+/// Separator of the names within a node's id.
+const ID_SEPARATOR: &str = " > ";
+
+/// A node of the report tree, i.e. a suite, context or example.
+struct Node {
+    id: String,
+    label: String,
+    kind: &'static str,
+    status: &'static str,
+    duration: Duration,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn suite(report: &SuiteReport) -> Node {
+        let header = report.get_header();
+        let id = header.name.to_string();
+        Node {
+            children: Node::children(&id, report.get_context()),
+            id,
+            label: header.name.to_string(),
+            kind: "suite",
+            status: status(report),
+            duration: report.get_wall_duration(),
+        }
+    }
+
+    /// The nodes of the context's blocks, with those of name-less scopes inlined.
+    fn children(parent_id: &str, report: &ContextReport) -> Vec<Node> {
+        let mut children = vec![];
+        for block in report.get_blocks() {
+            match *block {
+                BlockReport::Context(Some(ref header), ref report) => {
+                    let id = format!("{}{}{}", parent_id, ID_SEPARATOR, header.name);
+                    children.push(Node {
+                        children: Node::children(&id, report),
+                        id,
+                        label: header.name.to_string(),
+                        kind: "context",
+                        status: status(report),
+                        duration: report.get_wall_duration(),
+                    });
+                }
+                BlockReport::Context(None, ref report) => {
+                    children.extend(Node::children(parent_id, report));
+                }
+                BlockReport::Example(ref header, ref report) => children.push(Node {
+                    id: format!("{}{}{}", parent_id, ID_SEPARATOR, header.name),
+                    label: header.name.to_string(),
+                    kind: "example",
+                    status: status(report),
+                    duration: report.get_duration(),
+                    children: vec![],
+                }),
+            }
+        }
+        children
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push_str("{\"id\":");
+        write_string(out, &self.id);
+        out.push_str(",\"label\":");
+        write_string(out, &self.label);
+        let _ = write!(
+            out,
+            ",\"type\":\"{}\",\"status\":\"{}\",\"duration_ms\":{},\"children\":[",
+            self.kind,
+            self.status,
+            self.duration.as_millis()
+        );
+        for (index, child) in self.children.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            child.write(out);
+        }
+        out.push_str("]}");
+    }
+}
+
+fn status<R: Report>(report: &R) -> &'static str {
+    if report.is_failure() {
+        "failed"
+    } else if report.get_passed() == 0 && report.get_ignored() > 0 {
+        "ignored"
+    } else {
+        "passed"
+    }
+}
+
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl SuiteReport {
+    /// The report as a tree of nested JSON nodes, each with an `id`, `label`,
+    /// `type` (`suite`, `context` or `example`), `status`, `duration_ms` and `children`.
+    ///
+    /// Ids are the node's path (i.e. the names of the suite and enclosing contexts,
+    /// followed by the node's own name) joined by `" > "`, making them stable across runs.
+    /// Name-less scopes get no node of their own.
+    pub fn to_tree_json(&self) -> String {
+        let mut out = String::new();
+        Node::suite(self).write(&mut out);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use header::{ContextHeader, ContextLabel, ExampleHeader, ExampleLabel};
+    use header::{SuiteHeader, SuiteLabel};
+    use report::{ExampleReport, ExampleResult};
+
+    fn example(name: &'static str, result: ExampleResult) -> BlockReport {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        BlockReport::Example(header, ExampleReport::from(result))
+    }
+
+    fn context(name: Option<&'static str>, blocks: Vec<BlockReport>) -> BlockReport {
+        let header = name.map(|name| ContextHeader::new(ContextLabel::Context, name));
+        BlockReport::Context(header, ContextReport::from_blocks(blocks))
+    }
+
+    #[test]
+    fn it_nests_nodes_like_the_suite() {
+        // arrange
+        let root = ContextReport::from_blocks(vec![
+            example("passes", ExampleResult::Success),
+            context(
+                Some("a \"context\""),
+                vec![context(
+                    None,
+                    vec![example("fails", ExampleResult::failure(None))],
+                )],
+            ),
+        ]);
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root);
+        // act
+        let json = report.to_tree_json();
+        // assert
+        let expected = concat!(
+            r#"{"id":"suite","label":"suite","type":"suite","status":"failed","duration_ms":0,"children":["#,
+            r#"{"id":"suite > passes","label":"passes","type":"example","status":"passed","duration_ms":0,"children":[]},"#,
+            r#"{"id":"suite > a \"context\"","label":"a \"context\"","type":"context","status":"failed","duration_ms":0,"children":["#,
+            r#"{"id":"suite > a \"context\" > fails","label":"fails","type":"example","status":"failed","duration_ms":0,"children":[]}"#,
+            r#"]}]}"#
+        );
+        assert_eq!(expected, json);
+    }
+
+    #[test]
+    fn it_escapes_strings() {
+        // arrange
+        let mut out = String::new();
+        // act
+        write_string(&mut out, "a \"b\"\\\n\u{1}");
+        // assert
+        assert_eq!(r#""a \"b\"\\\n\u0001""#, out);
+    }
+}
@@ -2,12 +2,15 @@
 
 mod context;
 mod example;
+mod json;
+mod soft_assertions;
 mod suite;
 
-pub use time::Duration;
+pub use std::time::Duration;
 
 pub use report::context::*;
 pub use report::example::*;
+pub use report::soft_assertions::*;
 pub use report::suite::*;
 
 use header::ContextHeader;
@@ -22,10 +25,28 @@ pub trait Report {
     fn get_failed(&self) -> u32;
     fn get_ignored(&self) -> u32;
 
+    /// The number of examples, regardless of their results.
+    fn get_total(&self) -> u32 {
+        self.get_passed() + self.get_failed() + self.get_ignored()
+    }
+
+    /// The share of examples that passed, from `0.0` to `1.0`, counting ignored ones as not passed.
+    ///
+    /// Reports without any examples have a success rate of `0.0`.
+    fn success_rate(&self) -> f64 {
+        f64::from(self.get_passed()) / f64::from(self.get_total().max(1))
+    }
+
     fn get_duration(&self) -> Duration;
+
+    /// The summed up durations of all examples, regardless of them having run in parallel.
+    fn get_cpu_duration(&self) -> Duration {
+        self.get_duration()
+    }
 }
 
 /// `BlockReport` holds the results of a context block's test execution.
+#[must_use]
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum BlockReport {
     Context(Option<ContextHeader>, context::ContextReport),
@@ -39,6 +60,18 @@ impl BlockReport {
             BlockReport::Example(_, _) => None,
         }
     }
+
+    /// The report with all of its durations zeroed.
+    pub fn without_durations(&self) -> BlockReport {
+        match self {
+            BlockReport::Context(ref header, ref report) => {
+                BlockReport::Context(header.clone(), report.without_durations())
+            }
+            BlockReport::Example(ref header, ref report) => {
+                BlockReport::Example(header.clone(), report.without_durations())
+            }
+        }
+    }
 }
 
 impl Report for BlockReport {
@@ -83,4 +116,11 @@ impl Report for BlockReport {
             BlockReport::Example(_, ref report) => report.get_duration(),
         }
     }
+
+    fn get_cpu_duration(&self) -> Duration {
+        match self {
+            BlockReport::Context(_, ref report) => report.get_cpu_duration(),
+            BlockReport::Example(_, ref report) => report.get_cpu_duration(),
+        }
+    }
 }
@@ -0,0 +1,165 @@
+use report::{ExampleResult, IntoExampleResult};
+
+/// Accumulator of an example's assertions, checking all of them rather than
+/// stopping at the first failing one.
+///
+/// The example passes only if all of its assertions do, and otherwise fails
+/// with the messages of all failed ones.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::SoftAssertions;
+/// #
+/// # pub fn main() {
+/// rspec::run(&rspec::describe("a vector", vec![1, 2, 3], |ctx| {
+///     ctx.it("holds the expected values", |vec| {
+///         let mut assertions = SoftAssertions::new();
+///         assertions
+///             .check(rspec::expect_eq(3, vec.len()))
+///             .check(vec.contains(&2))
+///             .check(vec.first());
+///         assertions
+///     });
+/// }));
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SoftAssertions {
+    results: Vec<ExampleResult>,
+}
+
+impl SoftAssertions {
+    pub fn new() -> Self {
+        SoftAssertions::default()
+    }
+
+    /// Records the result of an assertion.
+    pub fn check<R: IntoExampleResult>(&mut self, result: R) -> &mut Self {
+        self.results.push(result.into_example_result());
+        self
+    }
+
+    /// Whether none of the assertions recorded so far failed.
+    pub fn is_success(&self) -> bool {
+        !self.results.iter().any(ExampleResult::is_failure)
+    }
+}
+
+/// rspec considers examples returning soft assertions a success if all of them passed,
+/// a failure (listing the failed ones) otherwise.
+impl From<SoftAssertions> for ExampleResult {
+    fn from(other: SoftAssertions) -> ExampleResult {
+        ExampleResult::from(other.results)
+    }
+}
+
+/// rspec considers examples returning multiple results a success if all of them are successes,
+/// a failure (listing the failed ones) otherwise.
+impl From<Vec<ExampleResult>> for ExampleResult {
+    fn from(other: Vec<ExampleResult>) -> ExampleResult {
+        let total = other.len();
+        let failures: Vec<String> = other
+            .into_iter()
+            .filter_map(|result| match result {
+                ExampleResult::Failure {
+                    message,
+                    expected,
+                    actual,
+                } => {
+                    let message = message.unwrap_or_else(|| "assertion failed".to_owned());
+                    Some(match (expected, actual) {
+                        (Some(expected), Some(actual)) => {
+                            format!("{} (expected: {}, actual: {})", message, expected, actual)
+                        }
+                        _ => message,
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        if failures.is_empty() {
+            return ExampleResult::Success;
+        }
+        let mut message = format!("{} of {} assertions failed:", failures.len(), total);
+        for (index, failure) in failures.iter().enumerate() {
+            message.push_str(&format!("\n  {}. {}", index + 1, failure));
+        }
+        ExampleResult::failure(Some(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use block::suite;
+    use report::{expect_eq, Report};
+    use runner::{ConfigurationBuilder, Runner};
+
+    #[test]
+    fn it_succeeds_without_failures() {
+        // arrange
+        let mut assertions = SoftAssertions::new();
+        // act
+        assertions.check(true).check(Some(42)).check(());
+        // assert
+        assert!(assertions.is_success());
+        assert_eq!(ExampleResult::Success, ExampleResult::from(assertions));
+    }
+
+    #[test]
+    fn it_lists_all_failed_assertions() {
+        // arrange
+        let config = ConfigurationBuilder::default()
+            .exit_on_failure(false)
+            .build()
+            .unwrap();
+        let runner = Runner::new(config, vec![]);
+        let suite = suite("a suite", (), |ctx| {
+            ctx.example("checks everything", |_| {
+                let mut assertions = SoftAssertions::new();
+                assertions
+                    .check(false)
+                    .check(expect_eq(2, 1 + 1))
+                    .check(Err::<(), _>("out of range"));
+                assertions
+            });
+        });
+        // act
+        let report = runner.run(&suite);
+        // assert
+        assert_eq!(1, report.get_failed());
+        let example = report
+            .get_context()
+            .find_example(&["checks everything"])
+            .unwrap();
+        let expected = ExampleResult::failure(Some(
+            "2 of 3 assertions failed:
+  1. assertion failed: `expected condition to be true`
+  2. out of range"
+                .to_owned(),
+        ));
+        assert_eq!(&expected, example.get_result());
+    }
+
+    #[test]
+    fn it_flattens_vectors_of_results() {
+        // arrange
+        let results = vec![
+            ExampleResult::Success,
+            ExampleResult::mismatch(None, "1".to_owned(), "2".to_owned()),
+        ];
+        // act
+        let result = ExampleResult::from(results);
+        // assert
+        let expected = ExampleResult::failure(Some(
+            "1 of 2 assertions failed:
+  1. assertion failed (expected: 1, actual: 2)"
+                .to_owned(),
+        ));
+        assert_eq!(expected, result);
+    }
+}
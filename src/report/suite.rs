@@ -1,13 +1,31 @@
-use time::Duration;
+use std::time::Duration;
 
-use header::SuiteHeader;
-use report::{ContextReport, Report};
+use header::{ContextHeader, ContextLabel, SuiteHeader, SuiteLabel};
+use report::{BlockReport, ContextReport, Report};
+
+/// A failed example, as listed by [`SuiteReport::failures`](struct.SuiteReport.html#method.failures).
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Failure {
+    /// The names of the example's enclosing contexts (excluding the suite itself
+    /// and name-less scopes), followed by the example's name.
+    pub path: Vec<String>,
+    /// The message of the example's failure, if any.
+    pub message: Option<String>,
+    pub duration: Duration,
+}
 
 /// `SuiteReport` holds the results of a context suite's test execution.
+#[must_use]
 #[derive(PartialEq, Eq, Clone, Debug, new)]
 pub struct SuiteReport {
     header: SuiteHeader,
     context: ContextReport,
+    #[new(default)]
+    elapsed: Option<Duration>,
+    #[new(default)]
+    error: Option<String>,
+    #[new(default)]
+    observer_panics: Vec<String>,
 }
 
 impl SuiteReport {
@@ -18,15 +36,126 @@ impl SuiteReport {
     pub fn get_context(&self) -> &ContextReport {
         &self.context
     }
+
+    /// A report of a suite without any blocks.
+    pub fn empty(header: SuiteHeader) -> Self {
+        SuiteReport::new(header, ContextReport::empty())
+    }
+
+    /// The real time elapsed while evaluating the suite.
+    pub fn get_wall_duration(&self) -> Duration {
+        self.context.get_wall_duration()
+    }
+
+    /// The real time elapsed for the whole run of the suite, from before notifying
+    /// observers of its start until after its last block (and hook) finished.
+    ///
+    /// Only available for reports created by a [`Runner`](../runner/struct.Runner.html).
+    pub fn get_elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    pub(crate) fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// The reason for failing the suite as a whole (rather than any of its examples), if any.
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub(crate) fn with_error(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// The messages of the panics observers raised during the run of the suite,
+    /// which the runner isolates them from rather than aborting the run.
+    pub fn get_observer_panics(&self) -> &[String] {
+        &self.observer_panics
+    }
+
+    pub(crate) fn with_observer_panics(mut self, observer_panics: Vec<String>) -> Self {
+        self.observer_panics = observer_panics;
+        self
+    }
+
+    /// The summed up durations of the suite's examples.
+    pub fn get_cpu_duration(&self) -> Duration {
+        self.context.get_cpu_duration()
+    }
+
+    /// The paths of all failed examples in declaration order.
+    ///
+    /// Each path consists of the names of the example's enclosing contexts
+    /// (excluding the suite itself and name-less scopes), followed by the example's name.
+    pub fn failed_examples(&self) -> Vec<Vec<String>> {
+        let failures = self.failures().into_iter();
+        failures.map(|failure| failure.path).collect()
+    }
+
+    /// All failed examples in declaration order, with their paths
+    /// (as for [`failed_examples`](#method.failed_examples)), messages and durations.
+    pub fn failures(&self) -> Vec<Failure> {
+        let mut failures = vec![];
+        self.context.collect_failures(&mut vec![], &mut failures);
+        failures
+    }
+
+    /// The report with all of its durations zeroed, e.g. for comparing it against
+    /// a "golden" report regardless of how long evaluating the suite took.
+    pub fn without_durations(&self) -> SuiteReport {
+        SuiteReport {
+            header: self.header.clone(),
+            context: self.context.without_durations(),
+            elapsed: self.elapsed.map(|_| Duration::default()),
+            error: self.error.clone(),
+            observer_panics: self.observer_panics.clone(),
+        }
+    }
+
+    /// The combined report of the suites run during a
+    /// [session](../runner/struct.Runner.html#method.begin_session),
+    /// holding each suite's report as a context named after the suite.
+    pub(crate) fn of_session(reports: Vec<SuiteReport>) -> SuiteReport {
+        let mut wall_duration = Duration::default();
+        let mut elapsed = None;
+        let mut error = None;
+        let mut observer_panics = vec![];
+        let mut blocks = vec![];
+        for report in reports {
+            wall_duration += report.get_wall_duration();
+            if let Some(duration) = report.elapsed {
+                elapsed = Some(elapsed.unwrap_or_default() + duration);
+            }
+            error = error.or(report.error);
+            observer_panics.extend(report.observer_panics);
+            let header = ContextHeader::new(ContextLabel::Context, report.header.name);
+            blocks.push(BlockReport::Context(Some(header), report.context));
+        }
+        SuiteReport {
+            header: SuiteHeader::new(SuiteLabel::Suite, "session"),
+            context: ContextReport::new(blocks, wall_duration),
+            elapsed,
+            error,
+            observer_panics,
+        }
+    }
+
+    /// Whether the suite contained no examples at all.
+    pub fn is_empty(&self) -> bool {
+        self.get_total() == 0
+    }
 }
 
 impl Report for SuiteReport {
     fn is_success(&self) -> bool {
-        self.context.is_success()
+        self.error.is_none() && self.context.is_success()
     }
 
     fn is_failure(&self) -> bool {
-        self.context.is_failure()
+        self.error.is_some() || self.context.is_failure()
     }
 
     fn get_passed(&self) -> u32 {
@@ -44,9 +173,137 @@ impl Report for SuiteReport {
     fn get_duration(&self) -> Duration {
         self.context.get_duration()
     }
+
+    fn get_cpu_duration(&self) -> Duration {
+        self.context.get_cpu_duration()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
+
+    use block::suite;
+    use header::{ExampleHeader, ExampleLabel};
+    use report::{BlockReport, ExampleReport, ExampleResult};
+    use runner::{ConfigurationBuilder, Runner};
+    use std::thread;
+
+    fn example(name: &'static str, result: ExampleResult) -> BlockReport {
+        let header = ExampleHeader::new(ExampleLabel::It, name);
+        BlockReport::Example(header, ExampleReport::from(result))
+    }
+
+    fn context(header: Option<ContextHeader>, blocks: Vec<BlockReport>) -> BlockReport {
+        BlockReport::Context(header, ContextReport::from_blocks(blocks))
+    }
+
+    #[test]
+    fn failed_examples() {
+        // arrange
+        let header = ContextHeader::new(ContextLabel::Context, "context");
+        let root = ContextReport::new(
+            vec![
+                example("passes", ExampleResult::Success),
+                example("fails", ExampleResult::failure(None)),
+                context(
+                    Some(header),
+                    vec![
+                        example("ignored", ExampleResult::Ignored(None)),
+                        context(
+                            None,
+                            vec![example("also fails", ExampleResult::failure(None))],
+                        ),
+                    ],
+                ),
+            ],
+            Duration::default(),
+        );
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root);
+        // act
+        let failures = report.failed_examples();
+        // assert
+        assert_eq!(
+            vec![
+                vec!["fails".to_owned()],
+                vec!["context".to_owned(), "also fails".to_owned()],
+            ],
+            failures
+        );
+    }
+
+    #[test]
+    fn failures() {
+        // arrange
+        let header = ContextHeader::new(ContextLabel::Context, "context");
+        let failure = ExampleResult::failure(Some("boom".to_owned()));
+        let slow_failure =
+            ExampleReport::new(ExampleResult::failure(None), Duration::from_millis(5));
+        let root = ContextReport::from_blocks(vec![
+            example("passes", ExampleResult::Success),
+            example("fails", failure),
+            context(
+                Some(header),
+                vec![BlockReport::Example(
+                    ExampleHeader::new(ExampleLabel::It, "also fails"),
+                    slow_failure,
+                )],
+            ),
+        ]);
+        let report = SuiteReport::new(SuiteHeader::new(SuiteLabel::Suite, "suite"), root);
+        // act
+        let failures = report.failures();
+        // assert
+        assert_eq!(
+            vec![
+                Failure {
+                    path: vec!["fails".to_owned()],
+                    message: Some("boom".to_owned()),
+                    duration: Duration::default(),
+                },
+                Failure {
+                    path: vec!["context".to_owned(), "also fails".to_owned()],
+                    message: None,
+                    duration: Duration::from_millis(5),
+                },
+            ],
+            failures
+        );
+    }
+
+    #[test]
+    fn without_durations() {
+        // arrange
+        let runner = Runner::new(
+            ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap(),
+            vec![],
+        );
+        let suite = suite("suite", (), |ctx| {
+            ctx.example("sleeps", |_| thread::sleep(Duration::from_millis(1)));
+            ctx.context("context", |ctx| {
+                ctx.example("fails", |_| false);
+            });
+        });
+        // act
+        let first = runner.run(&suite).without_durations();
+        let second = runner.run(&suite).without_durations();
+        // assert
+        assert_eq!(first, second);
+        assert_eq!(Some(Duration::default()), first.get_elapsed());
+        assert_eq!(Duration::default(), first.get_cpu_duration());
+    }
+
+    #[test]
+    fn is_empty() {
+        let header = SuiteHeader::new(SuiteLabel::Suite, "suite");
+        let empty = SuiteReport::empty(header.clone());
+        assert!(empty.is_empty());
+        let blocks = vec![example("passes", ExampleResult::Success)];
+        let non_empty = SuiteReport::new(header, ContextReport::from_blocks(blocks));
+        assert!(!non_empty.is_empty());
+    }
 }
@@ -0,0 +1,70 @@
+//! Cancellation tokens allow for stopping a run from another thread.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag for cancelling a run, shared by all of its clones,
+/// see [`Configuration::cancellation`](struct.Configuration.html#structfield.cancellation).
+///
+/// Once cancelled, the runner reports all blocks it hasn't started evaluating yet as ignored.
+/// Examples already being evaluated run to completion, unless they check the token themselves.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rspec;
+/// #
+/// # use rspec::runner::CancellationToken;
+/// #
+/// # pub fn main() {
+/// let token = CancellationToken::new();
+/// let configuration = rspec::ConfigurationBuilder::default()
+///     .cancellation(token.clone())
+///     .exit_on_failure(false)
+///     .build()
+///     .unwrap();
+/// let runner = rspec::Runner::new(configuration, vec![]);
+/// // e.g. upon a file having changed in watch mode:
+/// token.cancel();
+/// let report = runner.run(&rspec::suite("a test suite", (), |ctx| {
+///     ctx.example("an example", |_env| {
+///         // …
+///     });
+/// }));
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Cancels the run(s) of all runners configured with (a clone of) this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_shared_by_clones() {
+        // arrange
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        // act
+        clone.cancel();
+        // assert
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,123 @@
+//! Capturing of the panic output of examples, see
+//! [`ExampleReport::get_captured_output`](../report/struct.ExampleReport.html#method.get_captured_output).
+
+use std::cell::{Cell, RefCell};
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo) + Sync + Send + 'static>;
+
+thread_local! {
+    /// The panic output captured on the current thread since last being taken.
+    static CAPTURED_OUTPUT: RefCell<String> = const { RefCell::new(String::new()) };
+    /// Whether the current thread is evaluating blocks of a run swallowing panics.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The process-wide panic hook is shared by all runs, so concurrent runs
+/// (e.g. on a shared runner) keep track of who still needs the capturing hook,
+/// and of the hook it replaced.
+struct HookState {
+    users: usize,
+    previous: Option<Arc<PanicHook>>,
+}
+
+static HOOK_STATE: Mutex<HookState> = Mutex::new(HookState {
+    users: 0,
+    previous: None,
+});
+
+/// Installs a panic hook capturing (rather than printing) the output of panics
+/// raised while [`capturing`](fn.capturing.html), unless a concurrent run already did so.
+///
+/// Panics raised on threads not capturing (e.g. by other tests) still reach the replaced hook.
+pub(crate) fn install_hook() {
+    let mut state = HOOK_STATE.lock().unwrap_or_else(|err| err.into_inner());
+    if state.users == 0 {
+        let previous = Arc::new(panic::take_hook());
+        let forward = previous.clone();
+        state.previous = Some(previous);
+        panic::set_hook(Box::new(move |panic_info| {
+            if CAPTURING.with(Cell::get) {
+                CAPTURED_OUTPUT.with(|output| {
+                    output.borrow_mut().push_str(&format!("{}\n", panic_info));
+                });
+            } else {
+                forward(panic_info);
+            }
+        }));
+    }
+    state.users += 1;
+}
+
+/// Restores the panic hook replaced by [`install_hook`](fn.install_hook.html),
+/// once the last run needing the capturing hook is done.
+pub(crate) fn uninstall_hook() {
+    let mut state = HOOK_STATE.lock().unwrap_or_else(|err| err.into_inner());
+    if state.users == 0 {
+        return;
+    }
+    state.users -= 1;
+    if state.users == 0 {
+        if let Some(previous) = state.previous.take() {
+            // Dropping the capturing hook leaves the replaced one unshared:
+            drop(panic::take_hook());
+            if let Ok(previous) = Arc::try_unwrap(previous) {
+                panic::set_hook(previous);
+            }
+        }
+    }
+}
+
+/// Calls `body` with the panics raised by it on the current thread being captured
+/// (or not, if `enabled` is `false`, e.g. within a run not swallowing panics).
+pub(crate) fn capturing<F, R>(enabled: bool, body: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    /// Restores the thread's previous state, even if `body` panicked.
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CAPTURING.with(|capturing| capturing.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CAPTURING.with(|capturing| capturing.replace(enabled)));
+    body()
+}
+
+/// Takes the panic output captured on the current thread, if any.
+pub(crate) fn take_output() -> Option<String> {
+    let output = CAPTURED_OUTPUT.with(|output| output.replace(String::new()));
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_takes_nothing_without_panics() {
+        // arrange
+        let _ = take_output();
+        // act
+        let output = take_output();
+        // assert
+        assert_eq!(None, output);
+    }
+
+    #[test]
+    fn it_restores_the_capturing_state_of_the_thread() {
+        // act
+        let nested = capturing(true, || capturing(false, || CAPTURING.with(Cell::get)));
+        // assert
+        assert!(!nested);
+        assert!(!CAPTURING.with(Cell::get));
+    }
+}
@@ -0,0 +1,18 @@
+//! Clocks provide the runner with the current time, for measuring durations.
+
+use std::time::Instant;
+
+/// A source of the current time, see [`Runner::with_clock`](struct.Runner.html#method.with_clock).
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// The system's monotonic clock, used by default.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
@@ -1,15 +1,168 @@
 // derive_builder emits warnings otherwise:
 #![allow(unused_mut)]
 
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use runner::CancellationToken;
+
+/// A predicate on an example's path, that is the names of its enclosing contexts
+/// (excluding the suite itself and name-less scopes), followed by the example's name.
+pub type ExampleFilter = Arc<dyn Fn(&[&str]) -> bool + Send + Sync>;
+
+/// A transformation of a failed example's message (e.g. redacting secrets).
+pub type MessageFilter = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// How loggers lay out the evaluated blocks.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Layout {
+    /// A line per suite, context and example, indented by their nesting.
+    #[default]
+    Tree,
+    /// A line per example, prefixed with the names of its suite and contexts
+    /// (e.g. `a suite > a context > an example ... ok`) and not indented.
+    Flat,
+}
+
 /// A Runner's configuration.
-#[derive(Builder)]
+#[derive(Clone, Builder)]
+#[builder(build_fn(private, name = "build_unvalidated"))]
 pub struct Configuration {
     /// Whether the runner executes tests in parallel
-    #[builder(default = "true")]
+    /// (defaults to `false` without the `parallel` feature)
+    #[builder(default = "cfg!(feature = \"parallel\")")]
     pub parallel: bool,
     /// Whether the runner exits the procees upon encountering failures
     #[builder(default = "true")]
     pub exit_on_failure: bool,
+    /// The code the process exits with upon encountering failures (must be non-zero)
+    #[builder(default = "101")]
+    pub failure_exit_code: i32,
+    /// Whether the runner silences the panic hook (and thus panic traces on stderr) for panics
+    /// raised by its blocks, keeping their output for the examples' reports instead
+    #[builder(default = "true")]
+    pub swallow_panics: bool,
+    /// Which examples the runner executes (all, if none), reporting all others as ignored
+    #[builder(default, setter(strip_option))]
+    pub filter: Option<ExampleFilter>,
+    /// How the runner transforms the messages of failed examples before reporting them
+    #[builder(default, setter(strip_option))]
+    pub failure_message_filter: Option<MessageFilter>,
+    /// How often the runner retries a failing example before reporting it as failed
+    #[builder(default = "0")]
+    pub retries: u32,
+    /// Whether the runner evaluates each context's blocks in random order
+    /// (their reports are kept in declaration order regardless)
+    #[builder(default = "false")]
+    pub shuffle: bool,
+    /// The seed for shuffling, reproducing a previous run's order (random, if none)
+    #[builder(default, setter(strip_option))]
+    pub seed: Option<u64>,
+    /// Whether the runner reports all examples as ignored, without running them (nor any hooks)
+    #[builder(default = "false")]
+    pub dry_run: bool,
+    /// Whether the runner reports suites without any examples as failed
+    /// (e.g. due to a typo leaving all of them unregistered)
+    #[builder(default = "false")]
+    pub error_on_empty: bool,
+    /// Whether the runner hands examples declared via `example` (i.e. taking `&T`) their context's
+    /// environment itself, rather than a clone, when running serially (including contexts
+    /// evaluated serially within parallel runs, and as long as no `before_each`,
+    /// `after_each` or `around` hooks could mutate it)
+    #[builder(default = "false")]
+    pub reuse_environment_serial: bool,
+    /// The duration above which the runner reports otherwise passing examples as failed
+    /// (without interrupting them), e.g. for gating performance regressions
+    #[builder(default, setter(strip_option))]
+    pub max_example_duration: Option<Duration>,
+    /// The number of failed examples after which the runner stops evaluating any further ones,
+    /// reporting them as ignored instead (all get evaluated, if none)
+    #[builder(default, setter(strip_option))]
+    pub max_failures: Option<u32>,
+    /// Whether the runner appends the (pretty-printed) environment of failed examples
+    /// to their failure messages
+    #[builder(default = "false")]
+    pub dump_env_on_failure: bool,
+    /// The number of characters above which the runner truncates such environment dumps
+    #[builder(default = "4096")]
+    pub max_env_dump_len: usize,
+    /// Whether the runner evaluates the blocks of contexts having `before_all` or `after_all`
+    /// hooks serially (even if running in parallel), as such hooks usually imply state shared
+    /// by all of those blocks (contexts without them still get evaluated in parallel)
+    #[builder(default = "false")]
+    pub serial_contexts_with_all_hooks: bool,
+    /// How loggers lay out the evaluated blocks (defaults to `Layout::Tree`)
+    #[builder(default)]
+    pub output_layout: Layout,
+    /// The token for cancelling runs from another thread, upon which the runner reports
+    /// all blocks it hasn't started evaluating yet as ignored
+    #[builder(default, setter(strip_option))]
+    pub cancellation: Option<CancellationToken>,
+    /// The number of threads the runner evaluates blocks on when running in parallel
+    /// (read from the `RSPEC_THREADS` environment variable, if none, falling back to
+    /// rayon's current thread pool if that isn't set either)
+    #[builder(default, setter(strip_option))]
+    pub num_threads: Option<usize>,
+}
+
+/// The reasons for a [`ConfigurationBuilder`](struct.ConfigurationBuilder.html)
+/// to reject building a configuration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConfigError {
+    /// A `failure_exit_code` of zero would signal success upon failure.
+    ZeroFailureExitCode,
+    /// A `seed` got provided, but `shuffle` isn't enabled, so it would have no effect.
+    SeedWithoutShuffle,
+    /// A `max_failures` of zero would have the runner evaluate no examples at all.
+    ZeroMaxFailures,
+    /// A `num_threads` of zero would leave the runner without threads to evaluate examples on.
+    ZeroNumThreads,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::ZeroFailureExitCode => write!(f, "failure_exit_code must be non-zero"),
+            ConfigError::SeedWithoutShuffle => {
+                write!(f, "seed has no effect unless shuffle is enabled")
+            }
+            ConfigError::ZeroMaxFailures => write!(f, "max_failures must be non-zero"),
+            ConfigError::ZeroNumThreads => write!(f, "num_threads must be non-zero"),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+impl ConfigurationBuilder {
+    /// Builds a new `Configuration`, rejecting invalid (combinations of) options.
+    pub fn build(&self) -> Result<Configuration, ConfigError> {
+        self.validate()?;
+        // All fields have defaults, so building can't fail on missing ones:
+        Ok(self
+            .build_unvalidated()
+            .expect("all of the configuration's fields have defaults"))
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(0) = self.failure_exit_code {
+            return Err(ConfigError::ZeroFailureExitCode);
+        }
+        if let Some(Some(_)) = self.seed {
+            if self.shuffle != Some(true) {
+                return Err(ConfigError::SeedWithoutShuffle);
+            }
+        }
+        if let Some(Some(0)) = self.max_failures {
+            return Err(ConfigError::ZeroMaxFailures);
+        }
+        if let Some(Some(0)) = self.num_threads {
+            return Err(ConfigError::ZeroNumThreads);
+        }
+        Ok(())
+    }
 }
 
 impl Default for Configuration {
@@ -25,8 +178,23 @@ mod tests {
     #[test]
     fn default_with_builder() {
         let config = ConfigurationBuilder::default().build().unwrap();
-        assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, true);
+        assert_eq!(config.parallel, cfg!(feature = "parallel"));
+        assert!(config.exit_on_failure);
+        assert_eq!(config.failure_exit_code, 101);
+        assert!(config.swallow_panics);
+        assert!(config.filter.is_none());
+        assert!(config.failure_message_filter.is_none());
+        assert_eq!(config.retries, 0);
+        assert!(!config.shuffle);
+        assert!(config.seed.is_none());
+        assert!(!config.error_on_empty);
+        assert!(!config.reuse_environment_serial);
+        assert!(config.max_example_duration.is_none());
+        assert!(config.max_failures.is_none());
+        assert!(!config.dump_env_on_failure);
+        assert_eq!(config.max_env_dump_len, 4096);
+        assert!(!config.serial_contexts_with_all_hooks);
+        assert!(config.num_threads.is_none());
     }
 
     #[test]
@@ -43,21 +211,65 @@ mod tests {
     #[test]
     fn builder() {
         let config = ConfigurationBuilder::default().build().unwrap();
-        assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, true);
+        assert_eq!(config.parallel, cfg!(feature = "parallel"));
+        assert!(config.exit_on_failure);
 
         let config = ConfigurationBuilder::default()
             .parallel(false)
             .build()
             .unwrap();
-        assert_eq!(config.parallel, false);
-        assert_eq!(config.exit_on_failure, true);
+        assert!(!config.parallel);
+        assert!(config.exit_on_failure);
 
         let config = ConfigurationBuilder::default()
             .exit_on_failure(false)
             .build()
             .unwrap();
-        assert_eq!(config.parallel, true);
-        assert_eq!(config.exit_on_failure, false);
+        assert_eq!(config.parallel, cfg!(feature = "parallel"));
+        assert!(!config.exit_on_failure);
+
+        let config = ConfigurationBuilder::default()
+            .failure_exit_code(42)
+            .build()
+            .unwrap();
+        assert_eq!(config.failure_exit_code, 42);
+    }
+
+    #[test]
+    fn builder_rejects_zero_failure_exit_code() {
+        let result = ConfigurationBuilder::default().failure_exit_code(0).build();
+        assert_eq!(Some(ConfigError::ZeroFailureExitCode), result.err());
+    }
+
+    #[test]
+    fn builder_rejects_seed_without_shuffle() {
+        let result = ConfigurationBuilder::default().seed(42).build();
+        assert_eq!(Some(ConfigError::SeedWithoutShuffle), result.err());
+
+        let result = ConfigurationBuilder::default()
+            .seed(42)
+            .shuffle(true)
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn builder_rejects_zero_max_failures() {
+        let result = ConfigurationBuilder::default().max_failures(0).build();
+        assert_eq!(Some(ConfigError::ZeroMaxFailures), result.err());
+    }
+
+    #[test]
+    fn builder_rejects_zero_num_threads() {
+        let result = ConfigurationBuilder::default().num_threads(0).build();
+        assert_eq!(Some(ConfigError::ZeroNumThreads), result.err());
+    }
+
+    #[test]
+    fn config_error_fmt() {
+        assert_eq!(
+            "failure_exit_code must be non-zero",
+            ConfigError::ZeroFailureExitCode.to_string()
+        );
     }
 }
@@ -1,38 +1,96 @@
 //! Runners are responsible for executing a test suite's examples.
 
+mod cancellation;
+mod capture;
+mod clock;
 mod configuration;
 mod observer;
+mod reporter;
+mod shuffle;
 
+pub use runner::cancellation::*;
+pub use runner::clock::*;
 pub use runner::configuration::*;
 pub use runner::observer::*;
+pub use runner::reporter::*;
 
 use std::borrow::Borrow;
-use std::cell::Cell;
-use std::ops::{Deref, DerefMut};
-use std::panic;
+use std::env;
+use std::io;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 #[cfg(not(test))]
 use std::process;
-use std::sync::{Arc, Mutex};
-
-use time::Instant;
+use std::rc::Rc;
+use std::sync::atomic::{self, AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 use block::Block;
 use block::Context;
 use block::Example;
+use block::ExampleFunction;
 use block::Suite;
+use block::{catch_failure, panic_message, AroundHook, RunExample, SkipReason};
+use header::ExampleHeader;
 use report::ContextReport;
 use report::ExampleReport;
+use report::ExampleResult;
 use report::SuiteReport;
 use report::{BlockReport, Report};
 use visitor::TestSuiteVisitor;
 
+/// Callback for the position and report of each evaluated block of a context.
+type OnBlock<'a> = dyn Fn(usize, &BlockReport) + Sync + 'a;
+
+/// Locks the mutex, regardless of it being poisoned.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Sorts the reports of a context's blocks by their blocks' declaration index,
+/// regardless of the order they were evaluated (or completed) in.
+fn in_declaration_order(mut reports: Vec<(usize, BlockReport)>) -> Vec<BlockReport> {
+    reports.sort_by_key(|&(index, _)| index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+/// The source of the runners' identities, see `Runner::id`.
+static NEXT_RUNNER_ID: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+
+/// The state of a single run, kept apart from the runner it got started on,
+/// so that concurrent runs of a shared runner don't interfere with each other.
+#[derive(Default)]
+struct RunState {
+    /// The seed the run's blocks get shuffled with (if any).
+    seed: Option<u64>,
+    /// The run's failed examples, checked against `Configuration.max_failures`.
+    failures: AtomicU32,
+    /// The messages of the observers' panics during the run.
+    observer_panics: Mutex<Vec<String>>,
+}
+
 /// Runner for executing a test suite's examples.
 pub struct Runner {
     pub configuration: configuration::Configuration,
     observers: Vec<Arc<dyn RunnerObserver>>,
-    should_exit: Mutex<Cell<bool>>,
+    reporters: Vec<Arc<dyn Reporter>>,
+    clock: Arc<dyn Clock>,
+    should_exit: AtomicBool,
+    // Shared with the runners evaluating its runs:
+    io_error: Arc<Mutex<Option<io::Error>>>,
+    // The state of the run this runner evaluates, see `try_run`:
+    run: RunState,
+    // The seed and observer panics of the last completed run:
+    last_seed: Mutex<Option<u64>>,
+    last_observer_panics: Mutex<Vec<String>>,
+    // The reports of the current session's runs (if any), shared with derived runners:
+    session: Arc<Mutex<Option<Vec<SuiteReport>>>>,
+    // Shared with the runners evaluating its runs, for attaching serial observers to either:
+    id: usize,
 }
 
 impl Runner {
@@ -40,83 +98,598 @@ impl Runner {
         Runner {
             configuration,
             observers,
-            should_exit: Mutex::new(Cell::new(false)),
+            reporters: vec![],
+            clock: Arc::new(SystemClock),
+            should_exit: AtomicBool::new(false),
+            io_error: Arc::new(Mutex::new(None)),
+            run: RunState::default(),
+            last_seed: Mutex::new(None),
+            last_observer_panics: Mutex::new(vec![]),
+            session: Arc::new(Mutex::new(None)),
+            id: NEXT_RUNNER_ID.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    /// Attaches a reporter, to be handed the final report of each run.
+    pub fn with_reporter(mut self, reporter: Arc<dyn Reporter>) -> Runner {
+        self.reporters.push(reporter);
+        self
+    }
+
+    /// Has the runner call the callback with the final report once each run has finished
+    /// (e.g. for a global teardown), before exiting the process on failure.
+    ///
+    /// Like reporters, callbacks get called in the order they (and reporters) were attached in.
+    pub fn on_finish(self, callback: FinishCallback) -> Runner {
+        self.with_reporter(Arc::new(CallbackReporter(callback)))
+    }
+
+    /// Attaches an (owned) observer, e.g. a logger, without the need for wrapping it in an `Arc`.
+    ///
+    /// Observers that are to be inspected after the run still need to be shared
+    /// via [`new`](#method.new) or [`add_observer`](#method.add_observer).
+    pub fn with_observer<O>(mut self, observer: O) -> Runner
+    where
+        O: RunnerObserver + 'static,
+    {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Attaches an observer, to be notified of the events of subsequent runs.
+    pub fn add_observer(&mut self, observer: Arc<dyn RunnerObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Detaches all observers.
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    /// Has the runner measure durations using the given clock (rather than the system's).
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Runner {
+        self.clock = clock;
+        self
+    }
+
+    /// A runner with the given configuration, sharing this runner's observers, reporters and clock.
+    fn derived_runner(&self, configuration: Configuration) -> Runner {
+        let mut runner = Runner::new(configuration, self.observers.clone());
+        runner.reporters = self.reporters.clone();
+        runner.clock = self.clock.clone();
+        runner.session = self.session.clone();
+        runner
+    }
+
+    /// A runner for evaluating a single run with the given state, sharing everything else
+    /// (but its exit status) with this runner.
+    fn for_run(&self, run: RunState) -> Runner {
+        Runner {
+            configuration: self.configuration.clone(),
+            observers: self.observers.clone(),
+            reporters: self.reporters.clone(),
+            clock: self.clock.clone(),
+            should_exit: AtomicBool::new(false),
+            io_error: self.io_error.clone(),
+            run,
+            last_seed: Mutex::new(None),
+            last_observer_panics: Mutex::new(vec![]),
+            session: self.session.clone(),
+            id: self.id,
         }
     }
 }
 
 impl Runner {
     pub fn run<T>(&self, suite: &Suite<T>) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        self.record_outcome(self.try_run(suite))
+    }
+
+    /// Runs the given suite, returning `Err(report)` if any of its examples failed
+    /// or any observer vetoed the run (see [`RunnerObserver::finalize`](trait.RunnerObserver.html#method.finalize)).
+    ///
+    /// Unlike [`run`](#method.run) this never has the runner exit the process on failure,
+    /// leaving it to the caller to decide what to do with the report.
+    // Both variants carry the same (large) report, so boxing the error one wouldn't help:
+    #[allow(clippy::result_large_err)]
+    pub fn try_run<T>(&self, suite: &Suite<T>) -> Result<SuiteReport, SuiteReport>
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        // Each run gets evaluated with a state of its own, even if the runner is shared:
+        let run = self.for_run(RunState {
+            seed: self.draw_seed(),
+            ..RunState::default()
+        });
+        let result = run.evaluate(suite);
+        *lock(&self.last_seed) = run.run.seed;
+        *lock(&self.last_observer_panics) = run.observer_panics();
+        result
+    }
+
+    /// Evaluates a single run of the suite with the runner's run state, see `try_run`.
+    #[allow(clippy::result_large_err)]
+    fn evaluate<T>(&self, suite: &Suite<T>) -> Result<SuiteReport, SuiteReport>
     where
         T: Clone + Send + Sync + ::std::fmt::Debug,
     {
         let mut environment = suite.environment.clone();
         self.prepare_before_run();
-        let report = self.visit(suite, &mut environment);
+        let report = self.visit_on_threads(suite, &mut environment);
         self.clean_after_run();
-        if let Ok(mut mutex_guard) = self.should_exit.lock() {
-            *mutex_guard.deref_mut().get_mut() |= report.is_failure();
+        self.collect_io_errors();
+        let report = report.with_observer_panics(lock(&self.run.observer_panics).clone());
+        if let Some(ref mut reports) = *self.lock_session() {
+            reports.push(report.clone());
+        }
+        for reporter in &self.reporters {
+            reporter.report(&report);
+        }
+        if report.is_failure() {
+            Err(report)
+        } else {
+            Ok(report)
+        }
+    }
+
+    /// Re-runs only those examples of the suite that failed in a previous run of it, serially
+    /// and in declaration order. All other examples get reported as ignored.
+    pub fn rerun_failures<T>(&self, suite: &Suite<T>, report: &SuiteReport) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let failures = report.failed_examples();
+        let filter = move |path: &[&str]| {
+            failures
+                .iter()
+                .any(|failure| failure.iter().map(String::as_str).eq(path.iter().cloned()))
+        };
+        let mut configuration = self.configuration.clone();
+        configuration.parallel = false;
+        configuration.seed = self.seed().or(configuration.seed);
+        configuration.filter = Some(Arc::new(filter));
+        let runner = self.derived_runner(configuration);
+        self.record_outcome(runner.try_run(suite))
+    }
+
+    /// Runs only the example with the given path (i.e. the names of its enclosing contexts,
+    /// excluding the suite itself and name-less scopes, followed by its own name)
+    /// with all of its ancestors' hooks applied, returning its report.
+    ///
+    /// Returns `None` if the suite contains no such example.
+    ///
+    /// Observers only get notified of the example itself (and its enclosing contexts), and
+    /// unlike [`run`](#method.run) a failure of it never has the runner exit the process.
+    pub fn run_one<T>(&self, suite: &Suite<T>, path: &[&str]) -> Option<ExampleReport>
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        if !suite.context.contains_example(path) {
+            return None;
         }
+        // Rather than reporting all other examples as filtered out, leave them out altogether:
+        let suite = suite.filtered(|selected: &[&str]| selected == path);
+        let mut configuration = self.configuration.clone();
+        configuration.parallel = false;
+        configuration.seed = self.seed().or(configuration.seed);
+        let runner = self.derived_runner(configuration);
+        let report = match runner.try_run(&suite) {
+            Ok(report) | Err(report) => report,
+        };
+        report.get_context().find_example(path).cloned()
+    }
+
+    /// Runs the given suite serially (regardless of the configuration), having the given
+    /// serial observers observe it alongside the runner's own observers.
+    ///
+    /// Unlike [`RunnerObserver`](trait.RunnerObserver.html)s these need not be `Send + Sync`,
+    /// as serial runs never leave the calling thread.
+    pub fn run_serial<T>(
+        &self,
+        suite: &Suite<T>,
+        observers: Vec<Rc<dyn SerialRunnerObserver>>,
+    ) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let mut configuration = self.configuration.clone();
+        configuration.parallel = false;
+        let runner = self.derived_runner(configuration);
+        with_serial_observers(runner.id(), observers, || {
+            self.record_outcome(runner.try_run(suite))
+        })
+    }
+
+    /// Begins a session of runs, having observers treat the suites run until
+    /// [`end_session`](#method.end_session) as one, e.g. with loggers writing
+    /// a single `tests:` prefix and a single, combined summary.
+    ///
+    /// Beginning a session while in one discards the reports of the latter's runs.
+    pub fn begin_session(&self) {
+        *self.lock_session() = Some(vec![]);
+        self.broadcast(|handler| handler.enter_session(self));
+    }
+
+    /// Ends the current session, returning the combined report of the suites run during it,
+    /// each as a context named after its suite.
+    pub fn end_session(&self) -> SuiteReport {
+        let reports = self.lock_session().take().unwrap_or_default();
+        let report = SuiteReport::of_session(reports);
+        self.broadcast(|handler| handler.exit_session(self, &report));
         report
     }
 
+    /// Whether the runner is within a session,
+    /// see [`begin_session`](#method.begin_session).
+    pub fn in_session(&self) -> bool {
+        self.lock_session().is_some()
+    }
+
+    fn lock_session(&self) -> MutexGuard<'_, Option<Vec<SuiteReport>>> {
+        self.session
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Has the runner exit the process on dropping it (if configured to), if the run failed.
+    fn record_outcome(&self, result: Result<SuiteReport, SuiteReport>) -> SuiteReport {
+        match result {
+            Ok(report) => report,
+            Err(report) => {
+                self.should_exit.store(true, Ordering::SeqCst);
+                report
+            }
+        }
+    }
+
+    /// The seed the blocks of the current (or last) run got shuffled with,
+    /// if [`Configuration.shuffle`](struct.Configuration.html#structfield.shuffle) is enabled.
+    ///
+    /// Observers get handed the runner evaluating the current run, whose seed is that run's own
+    /// even if several runs of a shared runner are in progress.
+    pub fn seed(&self) -> Option<u64> {
+        self.run.seed.or(*lock(&self.last_seed))
+    }
+
+    /// The number of threads to evaluate blocks on, as configured or read from `RSPEC_THREADS`
+    /// (`None` for using the current thread pool).
+    fn num_threads(&self) -> Option<usize> {
+        self.configuration.num_threads.or_else(|| {
+            let threads = env::var("RSPEC_THREADS").ok()?.trim().parse().ok()?;
+            if threads > 0 {
+                Some(threads)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Evaluates the suite on a thread pool of its own, if running in parallel
+    /// on a given number of threads.
+    #[cfg(feature = "parallel")]
+    fn visit_on_threads<T>(&self, suite: &Suite<T>, environment: &mut T) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let threads = match self.num_threads() {
+            Some(threads) if self.configuration.parallel => threads,
+            _ => return self.visit(suite, environment),
+        };
+        match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool.install(|| self.visit(suite, environment)),
+            // Evaluating the suite at all beats failing for lack of threads:
+            Err(_) => self.visit(suite, environment),
+        }
+    }
+
+    // Without the `parallel` feature blocks always get evaluated serially.
+    #[cfg(not(feature = "parallel"))]
+    fn visit_on_threads<T>(&self, suite: &Suite<T>, environment: &mut T) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        self.visit(suite, environment)
+    }
+
+    /// The seed for shuffling the blocks of a new run with, if configured to.
+    fn draw_seed(&self) -> Option<u64> {
+        if self.configuration.shuffle {
+            Some(self.configuration.seed.unwrap_or_else(shuffle::random_seed))
+        } else {
+            None
+        }
+    }
+
+    /// The indices of the context's blocks in the order they are to be evaluated in.
+    fn evaluation_order<T>(&self, context: &Context<T>) -> Vec<usize> {
+        match self.run.seed {
+            Some(seed) => shuffle::shuffled_indices(context.blocks.len(), seed, &context.path),
+            None => (0..context.blocks.len()).collect(),
+        }
+    }
+
+    /// The first I/O error any of the runner's observers encountered
+    /// (e.g. while writing the logs), if any.
+    pub fn last_io_error(&self) -> Option<io::Error> {
+        let io_error = self.io_error.lock().ok()?;
+        io_error
+            .as_ref()
+            .map(|error| io::Error::new(error.kind(), error.to_string()))
+    }
+
+    /// The messages of the panics observers raised during the last run (if any)
+    /// or while being notified of sessions, which the runner isolates them from
+    /// rather than aborting the run.
+    ///
+    /// If several threads share the runner, prefer the run's own
+    /// [`SuiteReport::get_observer_panics`](../report/struct.SuiteReport.html#method.get_observer_panics).
+    pub fn observer_panics(&self) -> Vec<String> {
+        let mut panics = lock(&self.last_observer_panics).clone();
+        panics.extend(lock(&self.run.observer_panics).iter().cloned());
+        panics
+    }
+
+    fn collect_io_errors(&self) {
+        self.broadcast(|observer| {
+            if let Some(error) = observer.take_io_error() {
+                if let Ok(mut io_error) = self.io_error.lock() {
+                    io_error.get_or_insert(error);
+                }
+            }
+        });
+    }
+
+    /// Whether any observer vetoed the run of the given report.
+    ///
+    /// All observers get consulted, regardless of any previous one vetoing.
+    fn is_vetoed(&self, report: &SuiteReport) -> bool {
+        let mut vetoed = false;
+        self.broadcast(|observer| vetoed |= !observer.finalize(report));
+        vetoed
+    }
+
+    /// The identity of the runner for attaching serial observers to it.
+    fn id(&self) -> usize {
+        self.id
+    }
+
     fn broadcast<F>(&self, mut handler: F)
     where
-        F: FnMut(&dyn RunnerObserver),
+        F: FnMut(&dyn SerialRunnerObserver),
     {
         for observer in &self.observers {
-            handler(observer.borrow());
+            self.isolate(|| handler(&Forward(observer.borrow())));
+        }
+        for observer in serial_observers(self.id()) {
+            self.isolate(|| handler(&*observer));
+        }
+    }
+
+    /// Notifies an observer, recording rather than propagating its panic (if any).
+    fn isolate<F: FnOnce()>(&self, notification: F) {
+        if let Err(error) = catch_unwind(AssertUnwindSafe(notification)) {
+            let message = panic_message(&*error).unwrap_or_else(|| "observer panicked".to_owned());
+            lock(&self.run.observer_panics).push(message);
         }
     }
 
-    fn wrap_all<T, U, F>(&self, context: &Context<T>, environment: &mut T, wrapped_block: F) -> U
+    /// Wraps the block in the context's `before_all`/`after_all` hooks.
+    ///
+    /// Returns `Err(failure)` (skipping both, the block and `after_all`) if a `before_all` panicked.
+    fn wrap_all<T, U, F>(
+        &self,
+        context: &Context<T>,
+        environment: &mut T,
+        wrapped_block: F,
+    ) -> Result<U, ExampleResult>
     where
         F: Fn(&mut T) -> U,
     {
         for before_function in context.before_all.iter() {
-            before_function(environment);
+            let result = catch_failure(|| {
+                self.run_hook(
+                    context,
+                    HookKind::BeforeAll,
+                    environment,
+                    &**before_function,
+                );
+                ExampleResult::Success
+            });
+            if result.is_failure() {
+                return Err(result.map_message(|message| format!("before_all failed: {}", message)));
+            }
         }
         let result = wrapped_block(environment);
         for after_function in context.after_all.iter() {
-            after_function(environment);
+            self.run_hook(context, HookKind::AfterAll, environment, &**after_function);
         }
-        result
+        Ok(result)
     }
 
-    fn wrap_each<T, U, F>(&self, context: &Context<T>, environment: &mut T, wrapped_block: F) -> U
+    /// Runs the block surrounded by the context's `before_each`/`after_each` hooks,
+    /// returning `Err(reason)` if one of the former skipped it.
+    fn wrap_each<T, U, F>(
+        &self,
+        context: &Context<T>,
+        environment: &mut T,
+        wrapped_block: F,
+    ) -> Result<U, SkipReason>
     where
         F: Fn(&mut T) -> U,
     {
+        let mut skip_reason = None;
         for before_function in context.before_each.iter() {
-            before_function(environment);
+            skip_reason = self.run_hook(
+                context,
+                HookKind::BeforeEach,
+                environment,
+                &**before_function,
+            );
+            if skip_reason.is_some() {
+                break;
+            }
         }
-        let result = wrapped_block(environment);
+        let result = match skip_reason {
+            None => Ok(wrapped_block(environment)),
+            Some(reason) => Err(reason),
+        };
         for after_function in context.after_each.iter() {
-            after_function(environment);
+            self.run_hook(context, HookKind::AfterEach, environment, &**after_function);
         }
         result
     }
 
-    fn evaluate_blocks_parallel<T>(&self, context: &Context<T>, environment: &T) -> Vec<BlockReport>
+    fn run_hook<T, R>(
+        &self,
+        context: &Context<T>,
+        kind: HookKind,
+        environment: &mut T,
+        hook: &dyn Fn(&mut T) -> R,
+    ) -> R {
+        let header = context.header.as_ref();
+        self.broadcast(|handler| handler.enter_hook(self, kind, header));
+        let result = hook(environment);
+        self.broadcast(|handler| handler.exit_hook(self, kind, header));
+        result
+    }
+
+    fn wrap_around<T, U, F>(
+        &self,
+        hooks: &[AroundHook<T>],
+        environment: &mut T,
+        wrapped_block: &F,
+    ) -> Option<U>
+    where
+        F: Fn(&mut T) -> U,
+    {
+        match hooks.split_first() {
+            None => Some(wrapped_block(environment)),
+            Some((hook, inner_hooks)) => {
+                let mut result = None;
+                hook(
+                    environment,
+                    RunExample::new(&mut |environment| {
+                        result = self.wrap_around(inner_hooks, environment, wrapped_block);
+                    }),
+                );
+                result
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn evaluate_blocks_parallel<T>(
+        &self,
+        context: &Context<T>,
+        environment: &T,
+        on_block: Option<&OnBlock>,
+    ) -> Vec<BlockReport>
     where
         T: Clone + Send + Sync + ::std::fmt::Debug,
     {
-        context
-            .blocks
-            .par_iter()
-            .map(|block| self.evaluate_block(block, context, environment))
-            .collect()
+        let reports = self
+            .evaluation_order(context)
+            .into_par_iter()
+            .map(|index| {
+                let report = capture::capturing(self.configuration.swallow_panics, || {
+                    self.evaluate_block(&context.blocks[index], context, environment)
+                });
+                if let Some(on_block) = on_block {
+                    on_block(index, &report);
+                }
+                (index, report)
+            })
+            .collect();
+        in_declaration_order(reports)
     }
 
-    fn evaluate_blocks_serial<T>(&self, context: &Context<T>, environment: &T) -> Vec<BlockReport>
+    // Without the `parallel` feature blocks always get evaluated serially.
+    #[cfg(not(feature = "parallel"))]
+    fn evaluate_blocks_parallel<T>(
+        &self,
+        context: &Context<T>,
+        environment: &T,
+        on_block: Option<&OnBlock>,
+    ) -> Vec<BlockReport>
     where
         T: Clone + Send + Sync + ::std::fmt::Debug,
     {
-        context
-            .blocks
-            .iter()
-            .map(|block| self.evaluate_block(block, context, environment))
-            .collect()
+        self.evaluate_blocks_serial(context, environment, on_block)
+    }
+
+    fn evaluate_blocks_serial<T>(
+        &self,
+        context: &Context<T>,
+        environment: &T,
+        on_block: Option<&OnBlock>,
+    ) -> Vec<BlockReport>
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let reports = self
+            .evaluation_order(context)
+            .into_iter()
+            .map(|index| {
+                let report = capture::capturing(self.configuration.swallow_panics, || {
+                    self.evaluate_block(&context.blocks[index], context, environment)
+                });
+                if let Some(on_block) = on_block {
+                    on_block(index, &report);
+                }
+                (index, report)
+            })
+            .collect();
+        in_declaration_order(reports)
+    }
+
+    /// Evaluates the context, calling `on_block` with the (declaration) index and report
+    /// of each of its blocks as soon as it has been evaluated.
+    fn visit_context<T>(
+        &self,
+        context: &Context<T>,
+        environment: &mut T,
+        on_block: Option<&OnBlock>,
+    ) -> ContextReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.enter_context(self, header));
+        }
+        let start_time = self.clock.now();
+        let reports: Vec<_> = if self.configuration.dry_run {
+            let result = ExampleResult::Ignored(Some("dry run".to_owned()));
+            context
+                .blocks
+                .iter()
+                .map(|block| self.skip_block(block, &result))
+                .collect()
+        } else {
+            self.wrap_all(context, environment, |environment| {
+                if self.is_parallel(context) {
+                    self.evaluate_blocks_parallel(context, environment, on_block)
+                } else {
+                    self.evaluate_blocks_serial(context, environment, on_block)
+                }
+            })
+            .unwrap_or_else(|failure| {
+                context
+                    .blocks
+                    .iter()
+                    .map(|block| self.skip_block(block, &failure))
+                    .collect()
+            })
+        };
+        let end_time = self.clock.now();
+        let elapsed_time = end_time - start_time;
+        let report = ContextReport::new(reports, elapsed_time);
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.exit_context(self, header, &report));
+        }
+        report
     }
 
     fn evaluate_block<T>(
@@ -128,21 +701,158 @@ impl Runner {
     where
         T: Clone + Send + Sync + ::std::fmt::Debug,
     {
+        if !self.is_selected(block) {
+            return self.ignore_block(block, "filtered out");
+        }
+        if self.has_reached_max_failures() {
+            return self.ignore_block(block, "maximum number of failures reached");
+        }
+        if self.is_cancelled() {
+            return self.ignore_block(block, "cancelled");
+        }
+        if let Block::Context(ref context) = *block {
+            if let Some((ref predicate, ref reason)) = context.skip_if {
+                if predicate() {
+                    return self.ignore_block(block, reason);
+                }
+            }
+        }
+        if let Block::Example(ref example) = *block {
+            if let Some(ref reason) = example.ignored {
+                return self.ignore_block(block, reason);
+            }
+        }
+        if let Block::Example(ref example) = *block {
+            if let ExampleFunction::Immutable(ref function) = example.function {
+                if self.shares_environment(context) {
+                    let report = self.evaluate_example(&example.header, || {
+                        self.with_environment_dump(function(environment), environment)
+                    });
+                    return BlockReport::Example(example.header.clone(), report);
+                }
+            }
+        }
         let mut environment = environment.clone();
-        self.wrap_each(context, &mut environment, |environment| {
-            self.visit(block, environment)
-        })
+        let report = self.wrap_around(&context.around, &mut environment, &|environment| {
+            self.wrap_each(context, environment, |environment| {
+                self.visit(block, environment)
+            })
+        });
+        match report {
+            Some(Ok(report)) => report,
+            Some(Err(reason)) => self.ignore_block(block, &reason),
+            None => self.ignore_block(block, "not run by its `around` block"),
+        }
+    }
+
+    /// Whether the context's blocks get evaluated in parallel.
+    fn is_parallel<T>(&self, context: &Context<T>) -> bool {
+        let has_all_hooks = !context.before_all.is_empty() || !context.after_all.is_empty();
+        self.configuration.parallel
+            && !context.serial
+            && !(self.configuration.serial_contexts_with_all_hooks && has_all_hooks)
+    }
+
+    /// Whether the context's read-only examples can be handed its environment itself,
+    /// rather than a clone, as none of the context's per-example hooks could mutate it.
+    fn shares_environment<T>(&self, context: &Context<T>) -> bool {
+        self.configuration.reuse_environment_serial
+            && !self.is_parallel(context)
+            && context.before_each.is_empty()
+            && context.after_each.is_empty()
+            && context.around.is_empty()
+    }
+
+    /// Whether the current run has had as many failed examples as the configuration allows.
+    fn has_reached_max_failures(&self) -> bool {
+        match self.configuration.max_failures {
+            Some(max_failures) => self.run.failures.load(Ordering::SeqCst) >= max_failures,
+            None => false,
+        }
+    }
+
+    /// Whether the configured cancellation token (if any) has been cancelled.
+    fn is_cancelled(&self) -> bool {
+        self.configuration
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Whether the block is (or contains) an example passing the configured filter.
+    fn is_selected<T>(&self, block: &Block<T>) -> bool {
+        let filter = match self.configuration.filter {
+            Some(ref filter) => filter,
+            None => return true,
+        };
+        match block {
+            Block::Example(ref example) => example.is_matching(&**filter),
+            Block::Context(ref context) => self.is_context_selected(context),
+            Block::Mapped(ref context) => context.is_selected(self),
+        }
+    }
+
+    /// Whether the context contains an example passing the configured filter.
+    pub(crate) fn is_context_selected<T>(&self, context: &Context<T>) -> bool {
+        context.blocks.iter().any(|block| self.is_selected(block))
+    }
+
+    /// Reports a block (and all of its children) as ignored for the given reason, without running it.
+    fn ignore_block<T>(&self, block: &Block<T>, reason: &str) -> BlockReport {
+        self.skip_block(block, &ExampleResult::Ignored(Some(reason.to_owned())))
+    }
+
+    /// Reports all of the block's examples with the given result, without evaluating them.
+    fn skip_block<T>(&self, block: &Block<T>, result: &ExampleResult) -> BlockReport {
+        match block {
+            Block::Example(ref example) => {
+                let header = &example.header;
+                self.broadcast(|handler| handler.enter_example(self, header));
+                let report = ExampleReport::from(result.clone());
+                self.broadcast(|handler| handler.exit_example(self, header, &report));
+                BlockReport::Example(header.clone(), report)
+            }
+            Block::Context(ref context) => self.skip_context(context, result),
+            Block::Mapped(ref context) => context.skip(self, result),
+        }
+    }
+
+    /// Reports all of the context's examples with the given result, without evaluating them.
+    pub(crate) fn skip_context<T>(
+        &self,
+        context: &Context<T>,
+        result: &ExampleResult,
+    ) -> BlockReport {
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.enter_context(self, header));
+        }
+        let reports = context
+            .blocks
+            .iter()
+            .map(|block| self.skip_block(block, result))
+            .collect();
+        let report = ContextReport::skipped(reports);
+        if let Some(ref header) = context.header {
+            self.broadcast(|handler| handler.exit_context(self, header, &report));
+        }
+        BlockReport::Context(context.header.clone(), report)
     }
 
     fn prepare_before_run(&self) {
-        panic::set_hook(Box::new(|_panic_info| {
-            // XXX panics already catched at the test call site, don't output the trace in stdout
-        }));
+        if !self.configuration.swallow_panics {
+            // Panics get reported by whatever hook is installed (stderr by default).
+            return;
+        }
+        // Panics already get caught at the test call site, so rather than printing their
+        // output (i.e. message and location), keep it for the examples' reports:
+        capture::install_hook();
     }
 
     fn clean_after_run(&self) {
-        // XXX reset panic hook back to default hook:
-        let _ = panic::take_hook();
+        if !self.configuration.swallow_panics {
+            return;
+        }
+        capture::uninstall_hook();
     }
 }
 
@@ -156,11 +866,7 @@ impl Default for Runner {
 
 impl Drop for Runner {
     fn drop(&mut self) {
-        let should_exit = if let Ok(mutex_guard) = self.should_exit.lock() {
-            mutex_guard.deref().get()
-        } else {
-            false
-        };
+        let should_exit = self.should_exit.load(Ordering::SeqCst);
 
         if self.configuration.exit_on_failure && should_exit {
             // XXX Cargo test failure returns 101.
@@ -170,15 +876,18 @@ impl Drop for Runner {
             // > like 1, which everybody uses). I don't expect this behavior can ever change.
             // > This behavior probably dates to before 2013,
             // > all the way back to the creation of compiletest." – @brson
+            //
+            // Hence 101 is the default, yet some environments may require a different code.
+            let exit_code = self.configuration.failure_exit_code;
             #[cfg(not(test))]
-            process::exit(101);
+            process::exit(exit_code);
             #[cfg(test)]
-            panic!("test suite failed !")
+            panic!("test suite failed with exit code {} !", exit_code)
         }
     }
 }
 
-impl<T> TestSuiteVisitor<Suite<T>> for Runner
+impl<'a, T> TestSuiteVisitor<Suite<'a, T>> for Runner
 where
     T: Clone + Send + Sync + ::std::fmt::Debug,
 {
@@ -186,17 +895,44 @@ where
     type Output = SuiteReport;
 
     fn visit(&self, suite: &Suite<T>, environment: &mut Self::Environment) -> Self::Output {
+        capture::capturing(self.configuration.swallow_panics, || {
+            self.visit_suite(suite, environment)
+        })
+    }
+}
+
+impl Runner {
+    fn visit_suite<T>(&self, suite: &Suite<T>, environment: &mut T) -> SuiteReport
+    where
+        T: Clone + Send + Sync + ::std::fmt::Debug,
+    {
+        let start_time = self.clock.now();
         self.broadcast(|handler| handler.enter_suite(self, &suite.header));
+        let on_block = |index: usize, report: &BlockReport| {
+            self.broadcast(|handler| handler.exit_suite_block(self, index, report));
+        };
         let report = SuiteReport::new(
             suite.header.clone(),
-            self.visit(&suite.context, environment),
-        );
+            self.visit_context(&suite.context, environment, Some(&on_block)),
+        )
+        .with_elapsed(self.clock.now() - start_time);
+        let report = if self.configuration.error_on_empty && suite.num_examples() == 0 {
+            report.with_error(format!("{} contains no examples", suite.header))
+        } else {
+            report
+        };
+        // Observers get told about a veto via the report, e.g. for summing it up as failed:
+        let report = if self.is_vetoed(&report) && report.get_error().is_none() {
+            report.with_error("vetoed by an observer".to_owned())
+        } else {
+            report
+        };
         self.broadcast(|handler| handler.exit_suite(self, &suite.header, &report));
         report
     }
 }
 
-impl<T> TestSuiteVisitor<Block<T>> for Runner
+impl<'a, T> TestSuiteVisitor<Block<'a, T>> for Runner
 where
     T: Clone + Send + Sync + ::std::fmt::Debug,
 {
@@ -215,11 +951,12 @@ where
                 let report = self.visit(context, &mut environment.clone());
                 BlockReport::Context(header, report)
             }
+            Block::Mapped(ref context) => context.visit(self, environment),
         }
     }
 }
 
-impl<T> TestSuiteVisitor<Context<T>> for Runner
+impl<'a, T> TestSuiteVisitor<Context<'a, T>> for Runner
 where
     T: Clone + Send + Sync + ::std::fmt::Debug,
 {
@@ -227,28 +964,11 @@ where
     type Output = ContextReport;
 
     fn visit(&self, context: &Context<T>, environment: &mut Self::Environment) -> Self::Output {
-        if let Some(ref header) = context.header {
-            self.broadcast(|handler| handler.enter_context(self, &header));
-        }
-        let start_time = Instant::now();
-        let reports: Vec<_> = self.wrap_all(context, environment, |environment| {
-            if self.configuration.parallel {
-                self.evaluate_blocks_parallel(context, environment)
-            } else {
-                self.evaluate_blocks_serial(context, environment)
-            }
-        });
-        let end_time = Instant::now();
-        let elapsed_time = end_time - start_time;
-        let report = ContextReport::new(reports, elapsed_time);
-        if let Some(ref header) = context.header {
-            self.broadcast(|handler| handler.exit_context(self, &header, &report));
-        }
-        report
+        self.visit_context(context, environment, None)
     }
 }
 
-impl<T> TestSuiteVisitor<Example<T>> for Runner
+impl<'a, T> TestSuiteVisitor<Example<'a, T>> for Runner
 where
     T: Clone + Send + Sync + ::std::fmt::Debug,
 {
@@ -256,14 +976,97 @@ where
     type Output = ExampleReport;
 
     fn visit(&self, example: &Example<T>, environment: &mut Self::Environment) -> Self::Output {
-        self.broadcast(|handler| handler.enter_example(self, &example.header));
-        let start_time = Instant::now();
-        let result = (example.function)(environment);
-        let end_time = Instant::now();
-        let elapsed_time = end_time - start_time;
-        let report = ExampleReport::new(result, elapsed_time);
-        self.broadcast(|handler| handler.exit_example(self, &example.header, &report));
-        report
+        match example.function {
+            ExampleFunction::Immutable(ref function) => self
+                .evaluate_example(&example.header, || {
+                    self.with_environment_dump(function(environment), environment)
+                }),
+            ExampleFunction::Mutable(ref function) => {
+                // The environment already is the example's own clone, yet retries start over
+                // from the environment as it was before the first attempt:
+                let pristine = if self.configuration.retries > 0 {
+                    Some(environment.clone())
+                } else {
+                    None
+                };
+                let mut retrying = false;
+                self.evaluate_example(&example.header, || {
+                    if let (true, Some(pristine)) = (retrying, pristine.as_ref()) {
+                        *environment = pristine.clone();
+                    }
+                    retrying = true;
+                    let result = function(environment);
+                    self.with_environment_dump(result, environment)
+                })
+            }
+        }
+    }
+}
+
+impl Runner {
+    /// Evaluates the example of the given header by calling `attempt`
+    /// (again, for as many retries as the configuration allows it to fail).
+    fn evaluate_example<F>(&self, header: &ExampleHeader, mut attempt: F) -> ExampleReport
+    where
+        F: FnMut() -> ExampleResult,
+    {
+        self.broadcast(|handler| handler.enter_example(self, header));
+        let start_time = self.clock.now();
+        let mut failed_attempts = vec![];
+        // Discard any output not captured on behalf of this example:
+        let _ = capture::take_output();
+        let result = loop {
+            let result = attempt();
+            if !result.is_failure() || failed_attempts.len() as u32 >= self.configuration.retries {
+                break result;
+            }
+            failed_attempts.push(result);
+        };
+        let result = match self.configuration.failure_message_filter {
+            Some(ref filter) => result.map_message(|message| filter(message)),
+            None => result,
+        };
+        let end_time = self.clock.now();
+        let elapsed_time = end_time - start_time;
+        let result = match self.configuration.max_example_duration {
+            Some(budget) if result.is_success() && elapsed_time > budget => {
+                ExampleResult::failure(Some(format!(
+                    "exceeded duration budget of {}ms (took {}ms)",
+                    budget.as_millis(),
+                    elapsed_time.as_millis()
+                )))
+            }
+            _ => result,
+        };
+        if result.is_failure() {
+            self.run.failures.fetch_add(1, Ordering::SeqCst);
+        }
+        let captured_output = capture::take_output().filter(|_| result.is_failure());
+        let report = ExampleReport::new(result, elapsed_time)
+            .with_failed_attempts(failed_attempts)
+            .with_captured_output(captured_output);
+        self.broadcast(|handler| handler.exit_example(self, header, &report));
+        report
+    }
+}
+
+impl Runner {
+    /// Appends the pretty-printed environment to the result if it's a failure
+    /// and the configuration asks for it, truncated to the configured length.
+    fn with_environment_dump<T>(&self, result: ExampleResult, environment: &T) -> ExampleResult
+    where
+        T: ::std::fmt::Debug,
+    {
+        if !self.configuration.dump_env_on_failure || !result.is_failure() {
+            return result;
+        }
+        let dump = format!("{:#?}", environment);
+        let max_len = self.configuration.max_env_dump_len;
+        let dump = match dump.char_indices().nth(max_len) {
+            Some((end, _)) => format!("{}... (truncated)", &dump[..end]),
+            None => dump,
+        };
+        result.with_note(&format!("environment: {}", dump))
     }
 }
 
@@ -300,7 +1103,7 @@ mod tests {
                 // act
                 runner.broadcast(|_| has_been_called.store(true, Ordering::SeqCst));
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -347,7 +1150,7 @@ mod tests {
                 runner.broadcast(|observer| observer.enter_suite(&runner, &expected.clone()));
                 // assert
                 let lock = spy1.events.lock().expect("no dangling threads");
-                let res = (*lock).get(0).expect("to have been called once");
+                let res = (*lock).first().expect("to have been called once");
                 assert_eq!(&("enter_suite", expected), res);
             }
         }
@@ -355,6 +1158,7 @@ mod tests {
         mod wrap_each {
             use super::*;
 
+            use block::suite;
             use std::sync::atomic::*;
 
             #[test]
@@ -362,7 +1166,7 @@ mod tests {
                 // arrange
                 let runner = Runner::default();
                 // act
-                runner.wrap_each(&Context::default(), &mut (), |_| {})
+                let _ = runner.wrap_each(&Context::default(), &mut (), |_| {});
                 // assert
             }
 
@@ -372,11 +1176,11 @@ mod tests {
                 let runner = Runner::default();
                 let has_been_called = AtomicBool::new(false);
                 // act
-                runner.wrap_each(&Context::default(), &mut (), |_| {
+                let _ = runner.wrap_each(&Context::default(), &mut (), |_| {
                     has_been_called.store(true, Ordering::SeqCst)
                 });
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -388,9 +1192,9 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.before_each(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_each(&context, &mut (), |_| ());
+                let _ = runner.wrap_each(&context, &mut (), |_| ());
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -402,9 +1206,9 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.after_each(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_each(&context, &mut (), |_| ());
+                let _ = runner.wrap_each(&context, &mut (), |_| ());
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -422,7 +1226,7 @@ mod tests {
                 context.before_each(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| ());
+                let _ = runner.wrap_each(&context, &mut (), |_| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -442,7 +1246,7 @@ mod tests {
                 context.after_each(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| ());
+                let _ = runner.wrap_each(&context, &mut (), |_| ());
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -459,7 +1263,7 @@ mod tests {
                 context.before_each(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| {
+                let _ = runner.wrap_each(&context, &mut (), |_| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
@@ -478,12 +1282,219 @@ mod tests {
                 context.after_each(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_each(&context, &mut (), |_| {
+                let _ = runner.wrap_each(&context, &mut (), |_| {
                     last_caller_handler2.store(2, Ordering::SeqCst);
                 });
                 // assert
                 assert_eq!(1, last_caller_id.load(Ordering::SeqCst));
             }
+
+            #[test]
+            fn it_skips_the_main_closure_when_a_before_each_hook_asks_to() {
+                // arrange
+                let runner = Runner::default();
+                let calls = Arc::new(Mutex::new(vec![]));
+                let (body_calls, after_calls) = (calls.clone(), calls.clone());
+                let mut context = Context::default();
+                context.before_each_or_skip(|_| Some("not today".to_owned()));
+                context.after_each(move |_| after_calls.lock().unwrap().push("after"));
+                // act
+                let result = runner.wrap_each(&context, &mut (), |_| {
+                    body_calls.lock().unwrap().push("body");
+                });
+                // assert
+                assert_eq!(Err("not today".to_owned()), result);
+                assert_eq!(vec!["after"], *calls.lock().unwrap());
+            }
+
+            #[test]
+            fn it_reports_skipped_examples_as_ignored() {
+                // arrange
+                let runner = Runner::default();
+                let suite = suite("a suite", false, |ctx| {
+                    ctx.before_each_or_skip(|flag| {
+                        if *flag {
+                            None
+                        } else {
+                            Some("flag is off".to_owned())
+                        }
+                    });
+                    ctx.example("is skipped", |_| -> bool { panic!("should not run") });
+                });
+                // act
+                let report = runner.try_run(&suite).unwrap();
+                // assert
+                assert_eq!(0, report.get_failed());
+                assert_eq!(1, report.get_ignored());
+            }
+
+            #[test]
+            fn it_only_skips_the_examples_whose_guard_asks_to() {
+                // arrange
+                fn guard(flag: &mut bool) -> Option<SkipReason> {
+                    if *flag {
+                        None
+                    } else {
+                        Some("flag is off".to_owned())
+                    }
+                }
+                let runner = Runner::default();
+                let hooks = Arc::new(Mutex::new(vec![]));
+                let (on_hooks, off_hooks) = (hooks.clone(), hooks.clone());
+                let suite = suite("a suite", false, move |ctx| {
+                    ctx.context("on", move |ctx| {
+                        ctx.before_each(|flag| *flag = true);
+                        ctx.before_each_or_skip(guard);
+                        ctx.before_each(move |_| on_hooks.lock().unwrap().push("on"));
+                        ctx.example("passes", |flag| *flag);
+                    });
+                    ctx.context("off", move |ctx| {
+                        ctx.before_each_or_skip(guard);
+                        ctx.before_each(move |_| off_hooks.lock().unwrap().push("off"));
+                        ctx.example("is skipped", |_| -> bool { panic!("should not run") });
+                    });
+                });
+                // act
+                let report = runner.try_run(&suite).unwrap();
+                // assert
+                let result_of = |path: &[&str]| {
+                    let example = report.get_context().find_example(path);
+                    example.map(|example| example.get_result().clone())
+                };
+                assert_eq!(Some(ExampleResult::Success), result_of(&["on", "passes"]));
+                assert_eq!(
+                    Some(ExampleResult::Ignored(Some("flag is off".to_owned()))),
+                    result_of(&["off", "is skipped"])
+                );
+                assert_eq!(vec!["on"], *hooks.lock().unwrap());
+            }
+        }
+
+        mod run_hook {
+            use super::*;
+
+            use block::suite;
+            use header::ContextHeader;
+            use std::sync::atomic::*;
+
+            #[derive(Default)]
+            struct HookCounter {
+                enter: AtomicUsize,
+                exit: AtomicUsize,
+                kinds: Mutex<Vec<HookKind>>,
+            }
+
+            impl RunnerObserver for HookCounter {
+                fn enter_hook(&self, _runner: &Runner, kind: HookKind, _: Option<&ContextHeader>) {
+                    self.enter.fetch_add(1, Ordering::SeqCst);
+                    self.kinds.lock().unwrap().push(kind);
+                }
+
+                fn exit_hook(&self, _runner: &Runner, _kind: HookKind, _: Option<&ContextHeader>) {
+                    self.exit.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+
+            #[test]
+            fn it_broadcasts_hook_events() {
+                // arrange
+                let counter = Arc::new(HookCounter::default());
+                let runner = Runner::new(Configuration::default(), vec![counter.clone()]);
+                let suite = suite("a suite", (), |ctx| {
+                    ctx.context("a context", |ctx| {
+                        ctx.before_all(|_| {});
+                        ctx.before_each(|_| {});
+                        ctx.after_each(|_| {});
+                        ctx.after_all(|_| {});
+                        ctx.example("an example", |_| {});
+                    });
+                });
+                // act
+                let _ = runner.try_run(&suite);
+                // assert
+                assert_eq!(4, counter.enter.load(Ordering::SeqCst));
+                assert_eq!(4, counter.exit.load(Ordering::SeqCst));
+                assert_eq!(
+                    vec![
+                        HookKind::BeforeAll,
+                        HookKind::BeforeEach,
+                        HookKind::AfterEach,
+                        HookKind::AfterAll,
+                    ],
+                    *counter.kinds.lock().unwrap()
+                );
+            }
+        }
+
+        mod wrap_around {
+            use super::*;
+
+            use block::suite;
+
+            #[test]
+            fn it_wraps_each_example() {
+                // arrange
+                let runner = Runner::new(
+                    ConfigurationBuilder::default()
+                        .parallel(false)
+                        .build()
+                        .unwrap(),
+                    vec![],
+                );
+                let suite = suite("a suite", false, |ctx| {
+                    ctx.around(|flag, run| {
+                        *flag = true;
+                        run.call(flag);
+                        *flag = false;
+                    });
+                    ctx.example("observes the flag", |flag| *flag);
+                });
+                // act
+                let report = runner.try_run(&suite).unwrap();
+                // assert
+                assert_eq!(1, report.get_passed());
+            }
+
+            #[test]
+            fn it_runs_before_each_within_the_hook() {
+                // arrange
+                let runner = Runner::default();
+                let calls = Arc::new(Mutex::new(vec![]));
+                let (around_calls, before_calls) = (calls.clone(), calls.clone());
+                let mut context = Context::default();
+                context.around(move |env, run| {
+                    around_calls.lock().unwrap().push("enter");
+                    run.call(env);
+                    around_calls.lock().unwrap().push("exit");
+                });
+                context.before_each(move |_| before_calls.lock().unwrap().push("before"));
+                // act
+                let result = runner.wrap_around(&context.around, &mut (), &|env| {
+                    runner.wrap_each(&context, env, |_| 42)
+                });
+                // assert
+                assert_eq!(Some(Ok(42)), result);
+                assert_eq!(vec!["enter", "before", "exit"], *calls.lock().unwrap());
+            }
+
+            #[test]
+            fn it_ignores_blocks_not_run_by_the_hook() {
+                // arrange
+                let runner = Runner::default();
+                let has_been_called = Arc::new(AtomicBool::new(false));
+                let closure_bool_handler = has_been_called.clone();
+                let suite = suite("a suite", (), |ctx| {
+                    ctx.around(|_, _run| {});
+                    ctx.example("is never run", move |_| {
+                        closure_bool_handler.store(true, Ordering::SeqCst)
+                    });
+                });
+                // act
+                let report = runner.try_run(&suite).unwrap();
+                // assert
+                assert!(!has_been_called.load(Ordering::SeqCst));
+                assert_eq!(1, report.get_ignored());
+            }
         }
 
         mod wrap_all {
@@ -496,7 +1507,9 @@ mod tests {
                 // arrange
                 let runner = Runner::default();
                 // act
-                runner.wrap_all(&Context::default(), &mut (), |_| {})
+                runner
+                    .wrap_all(&Context::default(), &mut (), |_| {})
+                    .unwrap();
                 // assert
             }
 
@@ -506,11 +1519,13 @@ mod tests {
                 let runner = Runner::default();
                 let has_been_called = AtomicBool::new(false);
                 // act
-                runner.wrap_all(&Context::default(), &mut (), |_| {
-                    has_been_called.store(true, Ordering::SeqCst)
-                });
+                runner
+                    .wrap_all(&Context::default(), &mut (), |_| {
+                        has_been_called.store(true, Ordering::SeqCst)
+                    })
+                    .unwrap();
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -522,9 +1537,9 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.before_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), |_| ()).unwrap();
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -536,9 +1551,9 @@ mod tests {
                 let mut context = Context::default();
                 // act
                 context.after_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), |_| ()).unwrap();
                 // assert
-                assert_eq!(true, has_been_called.load(Ordering::SeqCst));
+                assert!(has_been_called.load(Ordering::SeqCst));
             }
 
             #[test]
@@ -556,7 +1571,7 @@ mod tests {
                 context.before_all(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), |_| ()).unwrap();
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -576,7 +1591,7 @@ mod tests {
                 context.after_all(move |_| {
                     closure_counter_handler2.fetch_add(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| ());
+                runner.wrap_all(&context, &mut (), |_| ()).unwrap();
                 // assert
                 assert_eq!(2, call_counter.load(Ordering::SeqCst));
             }
@@ -593,9 +1608,11 @@ mod tests {
                 context.before_all(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| {
-                    last_caller_handler2.store(2, Ordering::SeqCst);
-                });
+                runner
+                    .wrap_all(&context, &mut (), |_| {
+                        last_caller_handler2.store(2, Ordering::SeqCst);
+                    })
+                    .unwrap();
                 // assert
                 assert_eq!(2, last_caller_id.load(Ordering::SeqCst));
             }
@@ -612,84 +1629,2017 @@ mod tests {
                 context.after_all(move |_| {
                     last_caller_handler1.store(1, Ordering::SeqCst);
                 });
-                runner.wrap_all(&context, &mut (), |_| {
-                    last_caller_handler2.store(2, Ordering::SeqCst);
-                });
+                runner
+                    .wrap_all(&context, &mut (), |_| {
+                        last_caller_handler2.store(2, Ordering::SeqCst);
+                    })
+                    .unwrap();
                 // assert
                 assert_eq!(1, last_caller_id.load(Ordering::SeqCst));
             }
+
+            #[test]
+            fn it_skips_the_block_and_after_all_if_before_all_panics() {
+                // arrange
+                let runner = Runner::default();
+                let after_all_called = Arc::new(AtomicBool::new(false));
+                let closure_bool_handler = after_all_called.clone();
+                let mut context = Context::default();
+                context.before_all(|_| panic!("setup failed"));
+                context.after_all(move |_| closure_bool_handler.store(true, Ordering::SeqCst));
+                // act
+                let result = runner.wrap_all(&context, &mut (), |_| panic!("must not be called"));
+                // assert
+                let failure = result.unwrap_err();
+                assert!(failure.is_failure());
+                assert!(!after_all_called.load(Ordering::SeqCst));
+            }
         }
     }
 
-    mod impl_drop_for_runner {
+    mod try_run {
         use super::*;
 
+        use block::suite;
+
         #[test]
-        #[should_panic]
-        fn it_should_abort() {
+        fn it_returns_ok_for_a_passing_suite() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("a passing example", |_| true);
+            });
+            // act
+            let result = runner.try_run(&suite);
+            // assert
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn it_returns_err_for_a_failing_suite() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("a passing example", |_| true);
+                ctx.example("a failing example", |_| false);
+            });
+            // act
+            let result = runner.try_run(&suite);
+            // assert
+            let report = result.expect_err("suite should have failed");
+            assert_eq!(1, report.get_failed());
+        }
+    }
+
+    mod durations {
+        use super::*;
+
+        use block::suite;
+        use header::ExampleHeader;
+        #[cfg(feature = "parallel")]
+        use rayon::ThreadPoolBuilder;
+        use report::ExampleResult;
+        use std::thread;
+        use std::time::Duration;
+
+        #[test]
+        fn it_measures_the_duration_of_an_example() {
             // arrange
+            let runner = Runner::default();
+            let example = Example::new(ExampleHeader::default(), |_| {
+                thread::sleep(Duration::from_millis(20));
+                ExampleResult::Success
+            });
+            // act
+            let report = runner.visit(&example, &mut ());
+            // assert
+            assert!(report.get_duration() >= Duration::from_millis(20));
+        }
+
+        fn run_with_budget(budget: Duration) -> SuiteReport {
             let config = ConfigurationBuilder::default()
-                .exit_on_failure(true)
+                .parallel(false)
+                .exit_on_failure(false)
+                .max_example_duration(budget)
                 .build()
                 .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("sleeps", |_| thread::sleep(Duration::from_millis(30)));
+                ctx.example("fails slowly", |_| {
+                    thread::sleep(Duration::from_millis(30));
+                    false
+                });
+            });
+            runner.run(&suite)
+        }
+
+        #[test]
+        fn it_fails_examples_exceeding_the_duration_budget() {
+            // arrange
+            let budget = Duration::from_millis(10);
             // act
-            {
-                let runner = Runner::new(config, vec![]);
-                (*runner.should_exit.lock().unwrap()).set(true);
+            let report = run_with_budget(budget);
+            // assert
+            let example = report.get_context().find_example(&["sleeps"]).unwrap();
+            match *example.get_result() {
+                ExampleResult::Failure {
+                    message: Some(ref message),
+                    ..
+                } => assert!(message.starts_with("exceeded duration budget of 10ms")),
+                ref result => panic!("expected a failure, got {:?}", result),
             }
+            let example = report
+                .get_context()
+                .find_example(&["fails slowly"])
+                .unwrap();
+            let expected = ExampleResult::from(false);
+            assert_eq!(&expected, example.get_result());
+        }
+
+        #[test]
+        fn it_passes_examples_within_the_duration_budget() {
+            // arrange
+            let budget = Duration::from_secs(60);
+            // act
+            let report = run_with_budget(budget);
             // assert
-            // test should panic
+            assert_eq!((1, 1), (report.get_passed(), report.get_failed()));
+        }
+
+        #[test]
+        fn it_measures_the_elapsed_time_of_the_whole_run() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.before_all(|_| thread::sleep(Duration::from_millis(10)));
+                ctx.example("sleeps", |_| thread::sleep(Duration::from_millis(10)));
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            let elapsed = report.get_elapsed().unwrap();
+            assert!(elapsed >= Duration::from_millis(20));
+            assert!(elapsed >= report.get_wall_duration());
+        }
+
+        #[cfg(feature = "parallel")]
+        #[test]
+        fn it_measures_wall_time_below_cpu_time_in_parallel() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                for name in &["a", "b", "c", "d"] {
+                    ctx.example(*name, |_| thread::sleep(Duration::from_millis(50)));
+                }
+            });
+            let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+            // act
+            let report = pool.install(|| runner.run(&suite));
+            // assert
+            assert!(report.get_wall_duration() < report.get_cpu_duration());
         }
     }
 
-    mod impl_visitor_example_for_runner {
+    #[cfg(feature = "parallel")]
+    mod num_threads {
         use super::*;
 
-        use header::*;
-        use report::*;
-        use std::sync::atomic::*;
+        use block::suite;
+        use std::collections::HashSet;
+        use std::sync::Barrier;
+        use std::thread::{self, ThreadId};
 
-        #[derive(Default, Debug, Clone)]
-        struct SpyObserver {
-            enter_example: Arc<AtomicBool>,
-            exit_example: Arc<AtomicBool>,
+        #[derive(Clone, Debug)]
+        struct Environment {
+            barrier: Arc<Barrier>,
+            threads: Arc<Mutex<HashSet<ThreadId>>>,
+            pool_sizes: Arc<Mutex<Vec<usize>>>,
         }
-        impl RunnerObserver for SpyObserver {
-            fn enter_example(&self, _runner: &Runner, _header: &ExampleHeader) {
-                self.enter_example.store(true, Ordering::SeqCst)
-            }
 
-            fn exit_example(
-                &self,
-                _runner: &Runner,
-                _header: &ExampleHeader,
-                _report: &ExampleReport,
-            ) {
-                self.exit_example.store(true, Ordering::SeqCst)
-            }
+        fn run(runner: &Runner, threads: usize) -> Environment {
+            let environment = Environment {
+                barrier: Arc::new(Barrier::new(threads)),
+                threads: Arc::new(Mutex::new(HashSet::new())),
+                pool_sizes: Arc::new(Mutex::new(vec![])),
+            };
+            let suite = suite("a suite", environment.clone(), |ctx| {
+                for index in 0..threads {
+                    ctx.example(format!("example {}", index), |env| {
+                        // Deadlocks unless running on as many threads as there are examples:
+                        env.barrier.wait();
+                        env.threads.lock().unwrap().insert(thread::current().id());
+                        env.pool_sizes
+                            .lock()
+                            .unwrap()
+                            .push(rayon::current_num_threads());
+                    });
+                }
+            });
+            assert!(runner.try_run(&suite).is_ok());
+            environment
         }
 
         #[test]
-        fn it_can_be_called() {
+        fn it_reads_the_number_of_threads_from_the_environment() {
             // arrange
-            let runner = Runner::default();
-            let example = Example::fixture_success();
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            env::set_var("RSPEC_THREADS", "3");
             // act
+            let environment = run(&runner, 3);
             // assert
-            runner.visit(&example, &mut ());
+            env::remove_var("RSPEC_THREADS");
+            assert_eq!(3, environment.threads.lock().unwrap().len());
+            assert_eq!(vec![3; 3], *environment.pool_sizes.lock().unwrap());
         }
 
         #[test]
-        fn it_calls_observer_hooks() {
+        fn it_prefers_the_configured_number_of_threads() {
             // arrange
-            let spy = Arc::new(SpyObserver::default());
-            let runner = Runner::new(Configuration::default(), vec![spy.clone()]);
-            let example = Example::fixture_success();
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .num_threads(2)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            // act
+            let environment = run(&runner, 2);
+            // assert
+            assert_eq!(2, environment.threads.lock().unwrap().len());
+            assert_eq!(vec![2; 2], *environment.pool_sizes.lock().unwrap());
+        }
+    }
+
+    mod serial {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_evaluates_blocks_in_declaration_order() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let order = Arc::new(Mutex::new(vec![]));
+            let suite = suite("a suite", order.clone(), |ctx| {
+                ctx.example("first", |order| order.lock().unwrap().push(1));
+                ctx.context("a context", |ctx| {
+                    ctx.example("second", |order| order.lock().unwrap().push(2));
+                });
+                ctx.example("third", |order| order.lock().unwrap().push(3));
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert!(report.is_success());
+            assert_eq!(vec![1, 2, 3], *order.lock().unwrap());
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        #[test]
+        fn it_is_the_default_without_the_parallel_feature() {
+            assert!(!Configuration::default().parallel);
+        }
+    }
+
+    mod serial_contexts {
+        use super::*;
+
+        use block::suite;
+        #[cfg(feature = "parallel")]
+        use rayon::ThreadPoolBuilder;
+        #[cfg(feature = "parallel")]
+        use std::sync::atomic::AtomicUsize;
+        #[cfg(feature = "parallel")]
+        use std::thread;
+        #[cfg(feature = "parallel")]
+        use std::time::Duration;
+
+        /// The number of examples running at the same time, and the maximum thereof.
+        #[cfg(feature = "parallel")]
+        #[derive(Default)]
+        struct Concurrency {
+            current: AtomicUsize,
+            max: AtomicUsize,
+        }
+
+        #[cfg(feature = "parallel")]
+        impl Concurrency {
+            fn measure(&self) {
+                let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max.fetch_max(current, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(30));
+                self.current.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        #[cfg(feature = "parallel")]
+        #[test]
+        fn it_evaluates_contexts_with_all_hooks_serially() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .serial_contexts_with_all_hooks(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let hooked = Arc::new(Concurrency::default());
+            let unhooked = Arc::new(Concurrency::default());
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("with before_all", |ctx| {
+                    ctx.before_all(|_| {});
+                    for name in &["a", "b", "c", "d"] {
+                        let hooked = hooked.clone();
+                        ctx.example(*name, move |_| hooked.measure());
+                    }
+                });
+                ctx.context("without before_all", |ctx| {
+                    for name in &["a", "b", "c", "d"] {
+                        let unhooked = unhooked.clone();
+                        ctx.example(*name, move |_| unhooked.measure());
+                    }
+                });
+            });
+            let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+            // act
+            let report = pool.install(|| runner.run(&suite));
+            // assert
+            assert_eq!(8, report.get_passed());
+            assert_eq!(1, hooked.max.load(Ordering::SeqCst));
+            assert!(unhooked.max.load(Ordering::SeqCst) > 1);
+        }
+
+        #[cfg(feature = "parallel")]
+        #[test]
+        fn it_evaluates_serial_contexts_serially() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let serial = Arc::new(Concurrency::default());
+            let parallel = Arc::new(Concurrency::default());
+            let order = Arc::new(Mutex::new(vec![]));
+            let suite = suite("a suite", (), |ctx| {
+                ctx.serial_context("serial", |ctx| {
+                    for index in 0..4 {
+                        let (serial, order) = (serial.clone(), order.clone());
+                        ctx.example(format!("example {}", index), move |_| {
+                            serial.measure();
+                            order.lock().unwrap().push(index);
+                        });
+                    }
+                    ctx.context("nested", |ctx| {
+                        for index in 4..6 {
+                            let (serial, order) = (serial.clone(), order.clone());
+                            ctx.example(format!("example {}", index), move |_| {
+                                serial.measure();
+                                order.lock().unwrap().push(index);
+                            });
+                        }
+                    });
+                });
+                ctx.context("parallel", |ctx| {
+                    for name in &["a", "b", "c", "d"] {
+                        let parallel = parallel.clone();
+                        ctx.example(*name, move |_| parallel.measure());
+                    }
+                });
+            });
+            let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+            // act
+            let report = pool.install(|| runner.run(&suite));
+            // assert
+            assert_eq!(10, report.get_passed());
+            assert_eq!(1, serial.max.load(Ordering::SeqCst));
+            assert_eq!(vec![0, 1, 2, 3, 4, 5], *order.lock().unwrap());
+            assert!(parallel.max.load(Ordering::SeqCst) > 1);
+        }
+
+        #[test]
+        fn it_leaves_contexts_alone_by_default() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.before_all(|_| {});
+                ctx.after_all(|_| {});
+            });
+            // act
+            let is_parallel = runner.is_parallel(&suite.context);
+            // assert
+            assert!(is_parallel);
+        }
+    }
+
+    mod reporters {
+        use super::*;
+
+        use block::suite;
+
+        #[derive(Default)]
+        struct RecordingReporter {
+            reports: Mutex<Vec<SuiteReport>>,
+        }
+
+        impl Reporter for RecordingReporter {
+            fn report(&self, report: &SuiteReport) {
+                self.reports.lock().unwrap().push(report.clone());
+            }
+        }
+
+        #[test]
+        fn it_hands_the_final_report_to_reporters_once() {
+            // arrange
+            let reporter = Arc::new(RecordingReporter::default());
+            let runner = Runner::default().with_reporter(reporter.clone());
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("first", |_| {});
+                ctx.context("a context", |ctx| {
+                    ctx.example("second", |_| {});
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            let reports = reporter.reports.lock().unwrap();
+            assert_eq!(vec![report], *reports);
+        }
+
+        #[test]
+        fn it_calls_finish_callbacks_once() {
+            // arrange
+            let reports = Arc::new(Mutex::new(vec![]));
+            let recorded = reports.clone();
+            let runner = Runner::default().on_finish(Box::new(move |report: &SuiteReport| {
+                recorded.lock().unwrap().push(report.clone());
+            }));
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("first", |_| {});
+                ctx.example("second", |_| {});
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            assert_eq!(vec![report], *reports.lock().unwrap());
+        }
+    }
+
+    mod observers {
+        use super::*;
+
+        use block::suite;
+        use header::{ExampleHeader, SuiteHeader};
+        use logger::Logger;
+        use std::cell::RefCell;
+        use std::sync::atomic::*;
+
+        #[derive(Default)]
+        struct CountingObserver {
+            suites: AtomicUsize,
+        }
+
+        impl RunnerObserver for CountingObserver {
+            fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, _report: &SuiteReport) {
+                self.suites.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        /// Observer counting the suites it saw in a counter shared with the test.
+        struct ExitSuiteCallback(Arc<AtomicUsize>);
+
+        impl RunnerObserver for ExitSuiteCallback {
+            fn exit_suite(&self, _runner: &Runner, _header: &SuiteHeader, _report: &SuiteReport) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn it_notifies_observers_added_after_construction() {
+            // arrange
+            let observer = Arc::new(CountingObserver::default());
+            let mut runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+            });
+            let _ = runner.try_run(&suite);
+            // act
+            runner.add_observer(observer.clone());
+            let _ = runner.try_run(&suite);
+            // assert
+            assert_eq!(1, observer.suites.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_notifies_owned_observers() {
+            // arrange
+            let suites = Arc::new(AtomicUsize::new(0));
+            let observer = ExitSuiteCallback(suites.clone());
+            let runner = Runner::default()
+                .with_observer(observer)
+                .with_observer(Logger::new(io::sink()));
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+            });
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            assert!(report.is_ok());
+            assert_eq!(1, suites.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_no_longer_notifies_cleared_observers() {
+            // arrange
+            let observer = Arc::new(CountingObserver::default());
+            let mut runner = Runner::new(Configuration::default(), vec![observer.clone()]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("an example", |_| {});
+            });
+            // act
+            runner.clear_observers();
+            let _ = runner.try_run(&suite);
+            // assert
+            assert_eq!(0, observer.suites.load(Ordering::SeqCst));
+        }
+
+        /// Observer panicking upon exiting the example of the given name.
+        struct PanickingObserver(&'static str);
+
+        impl RunnerObserver for PanickingObserver {
+            fn exit_example(
+                &self,
+                _runner: &Runner,
+                header: &ExampleHeader,
+                _report: &ExampleReport,
+            ) {
+                if header.name == self.0 {
+                    panic!("failed to observe {:?}", self.0);
+                }
+            }
+        }
+
+        #[test]
+        fn it_isolates_panicking_observers() {
+            // arrange
+            let observer = Arc::new(CountingObserver::default());
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let observers: Vec<Arc<dyn RunnerObserver>> =
+                vec![Arc::new(PanickingObserver("second")), observer.clone()];
+            let runner = Runner::new(config, observers);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("first", |_| true);
+                ctx.example("second", |_| true);
+                ctx.example("third", |_| false);
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            assert_eq!((2, 1), (report.get_passed(), report.get_failed()));
+            assert_eq!(1, observer.suites.load(Ordering::SeqCst));
+            assert_eq!(
+                vec!["failed to observe \"second\"".to_owned()],
+                runner.observer_panics()
+            );
+            assert_eq!(runner.observer_panics(), report.get_observer_panics());
+            let _ = runner.try_run(&suite);
+            assert_eq!(1, runner.observer_panics().len());
+        }
+
+        #[test]
+        fn it_records_observer_panics_per_run() {
+            use std::sync::Barrier;
+            use std::thread;
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let observers: Vec<Arc<dyn RunnerObserver>> =
+                vec![Arc::new(PanickingObserver("second"))];
+            let runner = Arc::new(Runner::new(config, observers));
+            let barrier = Arc::new(Barrier::new(2));
+            let panicking = {
+                let (runner, barrier) = (runner.clone(), barrier.clone());
+                thread::spawn(move || {
+                    let suite = suite("a panicking suite", (), move |ctx| {
+                        ctx.example("second", |_| true);
+                        ctx.example("waits for the other run", move |_| {
+                            barrier.wait();
+                            true
+                        });
+                    });
+                    runner.run(&suite)
+                })
+            };
+            let suite = suite("a concurrent suite", (), move |ctx| {
+                ctx.example("waits for the other run", move |_| {
+                    barrier.wait();
+                    true
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert!(report.get_observer_panics().is_empty());
+            assert_eq!(
+                ["failed to observe \"second\"".to_owned()],
+                panicking.join().unwrap().get_observer_panics()
+            );
+        }
+
+        /// Observer vetoing every run, e.g. like a coverage gate would.
+        struct VetoingObserver;
+
+        impl RunnerObserver for VetoingObserver {
+            fn finalize(&self, _report: &SuiteReport) -> bool {
+                false
+            }
+        }
+
+        #[test]
+        fn it_fails_runs_vetoed_by_an_observer() {
+            // arrange
+            let observer = Arc::new(CountingObserver::default());
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let observers: Vec<Arc<dyn RunnerObserver>> =
+                vec![Arc::new(VetoingObserver), observer.clone()];
+            let runner = Runner::new(config, observers);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("passing", |_| true);
+            });
+            // act
+            let result = runner.try_run(&suite);
+            let report = runner.run(&suite);
+            // assert
+            assert!(result.is_err());
+            assert_eq!((1, 0), (report.get_passed(), report.get_failed()));
+            assert_eq!(Some("vetoed by an observer"), report.get_error());
+            assert_eq!(2, observer.suites.load(Ordering::SeqCst));
+            assert!(runner.should_exit.load(Ordering::SeqCst));
+        }
+
+        /// Serial observer keeping the names of the examples it saw in a `RefCell`.
+        #[derive(Default)]
+        struct RecordingObserver {
+            examples: RefCell<Vec<String>>,
+        }
+
+        impl SerialRunnerObserver for RecordingObserver {
+            fn exit_example(
+                &self,
+                _runner: &Runner,
+                header: &ExampleHeader,
+                _report: &ExampleReport,
+            ) {
+                self.examples.borrow_mut().push(header.name.to_string());
+            }
+        }
+
+        #[test]
+        fn it_notifies_serial_observers_of_serial_runs() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(true)
+                .build()
+                .unwrap();
+            let observer = Arc::new(CountingObserver::default());
+            let runner = Runner::new(config, vec![observer.clone()]);
+            let serial_observer = Rc::new(RecordingObserver::default());
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("first", |_| {});
+                ctx.context("a context", |ctx| {
+                    ctx.example("second", |_| {});
+                });
+            });
+            // act
+            let report = runner.run_serial(&suite, vec![serial_observer.clone()]);
+            let _ = runner.try_run(&suite);
+            // assert
+            assert_eq!(2, report.get_passed());
+            assert_eq!(vec!["first", "second"], *serial_observer.examples.borrow());
+            assert_eq!(2, observer.suites.load(Ordering::SeqCst));
+        }
+    }
+
+    mod before_all_failures {
+        use super::*;
+
+        use block::suite;
+        use std::sync::atomic::*;
+
+        #[test]
+        fn it_fails_only_the_affected_context() {
+            // arrange
+            let runner = Runner::new(
+                ConfigurationBuilder::default()
+                    .exit_on_failure(false)
+                    .build()
+                    .unwrap(),
+                vec![],
+            );
+            let call_counter = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", call_counter.clone(), |ctx| {
+                ctx.context("a broken context", |ctx| {
+                    ctx.before_all(|_| panic!("no database"));
+                    ctx.example("first", |counter| {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    });
+                    ctx.context("a nested context", |ctx| {
+                        ctx.example("second", |counter| {
+                            counter.fetch_add(1, Ordering::SeqCst);
+                        });
+                    });
+                });
+                ctx.context("a sibling context", |ctx| {
+                    ctx.example("third", |counter| {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    });
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            assert_eq!(1, call_counter.load(Ordering::SeqCst));
+            assert_eq!(1, report.get_passed());
+            assert_eq!(2, report.get_failed());
+            let broken = &report.get_context().get_blocks()[0];
+            let first = match broken.get_blocks().unwrap()[0] {
+                BlockReport::Example(_, ref report) => report.get_result().clone(),
+                ref block => panic!("expected an example report, got {:?}", block),
+            };
+            match first {
+                ExampleResult::Failure {
+                    message: Some(ref message),
+                    ..
+                } => assert!(message.starts_with("before_all failed: ")),
+                result => panic!("expected a failure with message, got {:?}", result),
+            }
+        }
+    }
+
+    mod swallow_panics {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_lets_panics_reach_the_panic_hook_when_disabled() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .swallow_panics(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("panics", |_| -> () { panic!("unexpected panic") });
+            });
+            // As if a concurrent run swallowing panics had installed the capturing hook:
+            capture::install_hook();
+            // act
+            let result = runner.try_run(&suite);
+            capture::uninstall_hook();
+            // assert
+            let report = result.unwrap_err();
+            let example = report.get_context().find_example(&["panics"]).unwrap();
+            assert!(example.is_failure());
+            assert_eq!(None, example.get_captured_output());
+        }
+    }
+
+    mod shared_runner {
+        use super::*;
+
+        use block::suite;
+        use header::{ContextHeader, ExampleHeader, SuiteHeader};
+        use std::collections::HashMap;
+        use std::thread::{self, ThreadId};
+
+        /// Observer recording the seeds and blocks of the runs on each thread.
+        #[derive(Default)]
+        struct RunRecorder {
+            seeds: Mutex<HashMap<ThreadId, Vec<Option<u64>>>>,
+            blocks: Mutex<HashMap<ThreadId, Vec<String>>>,
+        }
+
+        impl RunRecorder {
+            fn record_seed(&self, runner: &Runner) {
+                let mut seeds = self.seeds.lock().unwrap();
+                let seeds = seeds.entry(thread::current().id()).or_default();
+                seeds.push(runner.seed());
+            }
+
+            fn record_block(&self, name: &str) {
+                let mut blocks = self.blocks.lock().unwrap();
+                let blocks = blocks.entry(thread::current().id()).or_default();
+                blocks.push(name.to_owned());
+            }
+        }
+
+        impl RunnerObserver for RunRecorder {
+            fn enter_suite(&self, runner: &Runner, _header: &SuiteHeader) {
+                self.record_seed(runner);
+            }
+            fn exit_suite(&self, runner: &Runner, _header: &SuiteHeader, _report: &SuiteReport) {
+                self.record_seed(runner);
+            }
+            fn enter_context(&self, _runner: &Runner, header: &ContextHeader) {
+                self.record_block(&header.name);
+            }
+            fn enter_example(&self, _runner: &Runner, header: &ExampleHeader) {
+                self.record_block(&header.name);
+            }
+        }
+
+        fn configuration(seed: Option<u64>) -> Configuration {
+            let mut configuration = ConfigurationBuilder::default()
+                .parallel(false)
+                .shuffle(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            configuration.seed = seed;
+            configuration
+        }
+
+        fn shuffled_suite(failures: usize) -> Suite<'static, usize> {
+            suite("a suite", failures, |ctx| {
+                ctx.example("passes", |_| true);
+                ctx.example("fails", |failures| *failures == 0);
+                ctx.example("panics", |failures| -> bool {
+                    if *failures > 0 {
+                        panic!("panicking example")
+                    }
+                    true
+                });
+                for i in 0..8 {
+                    ctx.context(format!("context {}", i), |ctx| {
+                        for j in 0..4 {
+                            ctx.example(format!("example {}", j), |_| true);
+                        }
+                    });
+                }
+            })
+        }
+
+        #[test]
+        fn it_can_run_suites_from_several_threads_at_once() {
+            // arrange
+            let recorder = Arc::new(RunRecorder::default());
+            let runner = Arc::new(Runner::new(configuration(None), vec![recorder.clone()]));
+            let handles: Vec<_> = (0..2)
+                .map(|failures| {
+                    let runner = runner.clone();
+                    thread::spawn(move || {
+                        let report = runner.run(&shuffled_suite(failures));
+                        (thread::current().id(), report)
+                    })
+                })
+                .collect();
+            // act
+            let runs: Vec<_> = handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect();
+            // assert
+            let reports: Vec<_> = runs.iter().map(|(_, report)| report).collect();
+            assert_eq!((35, 0), (reports[0].get_passed(), reports[0].get_failed()));
+            assert_eq!((33, 2), (reports[1].get_passed(), reports[1].get_failed()));
+            assert!(runner.should_exit.load(Ordering::SeqCst));
+            for (failures, (thread, _)) in runs.iter().enumerate() {
+                let seeds = recorder.seeds.lock().unwrap()[thread].clone();
+                assert_eq!(2, seeds.len());
+                assert!(seeds[0].is_some());
+                assert_eq!(seeds[0], seeds[1]);
+                // The seed reported for the run reproduces its order:
+                let replay = Arc::new(RunRecorder::default());
+                let replay_runner = Runner::new(configuration(seeds[0]), vec![replay.clone()]);
+                let _ = replay_runner.run(&shuffled_suite(failures));
+                let current = thread::current().id();
+                assert_eq!(
+                    recorder.blocks.lock().unwrap()[thread],
+                    replay.blocks.lock().unwrap()[&current]
+                );
+            }
+        }
+    }
+
+    mod error_on_empty {
+        use super::*;
+
+        use block::suite;
+
+        fn configuration(error_on_empty: bool) -> Configuration {
+            ConfigurationBuilder::default()
+                .exit_on_failure(false)
+                .error_on_empty(error_on_empty)
+                .build()
+                .unwrap()
+        }
+
+        #[test]
+        fn it_fails_suites_without_examples() {
+            // arrange
+            let runner = Runner::new(configuration(true), vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("a typo'd context", |_| {});
+            });
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            let report = report.unwrap_err();
+            assert_eq!(0, report.get_failed());
+            assert_eq!(
+                Some("Suite \"a suite\" contains no examples"),
+                report.get_error()
+            );
+        }
+
+        #[test]
+        fn it_passes_suites_with_examples() {
+            // arrange
+            let runner = Runner::new(configuration(true), vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.context("an empty context", |_| {});
+                ctx.example("an example", |_| {});
+            });
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            assert_eq!(None, report.unwrap().get_error());
+        }
+
+        #[test]
+        fn it_passes_empty_suites_by_default() {
+            // arrange
+            let runner = Runner::new(configuration(false), vec![]);
+            let suite = suite("a suite", (), |_| {});
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            assert!(report.is_ok());
+        }
+    }
+
+    mod reuse_environment_serial {
+        use super::*;
+
+        use block::suite;
+        use std::fmt;
+        use std::sync::atomic::AtomicUsize;
+
+        /// Environment counting how often it got cloned.
+        struct CloneCounter(Arc<AtomicUsize>);
+
+        impl Clone for CloneCounter {
+            fn clone(&self) -> Self {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                CloneCounter(self.0.clone())
+            }
+        }
+
+        impl fmt::Debug for CloneCounter {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "CloneCounter")
+            }
+        }
+
+        fn count_clones(reuse: bool, body: fn(&mut Context<CloneCounter>)) -> usize {
+            count_clones_in(false, reuse, body)
+        }
+
+        fn count_clones_in(
+            parallel: bool,
+            reuse: bool,
+            body: fn(&mut Context<CloneCounter>),
+        ) -> usize {
+            let config = ConfigurationBuilder::default()
+                .parallel(parallel)
+                .reuse_environment_serial(reuse)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let clones = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", CloneCounter(clones.clone()), body);
+            let report = runner.try_run(&suite);
+            assert!(report.is_ok());
+            clones.load(Ordering::SeqCst)
+        }
+
+        #[test]
+        fn it_hands_read_only_examples_the_environment_itself() {
+            // arrange
+            let body = |ctx: &mut Context<CloneCounter>| {
+                for _ in 0..3 {
+                    ctx.example("reads the environment", |_| {});
+                }
+            };
+            // act
+            let (cloned, reused) = (count_clones(false, body), count_clones(true, body));
+            // assert
+            assert_eq!(cloned - 3, reused);
+        }
+
+        #[test]
+        fn it_still_clones_for_mutable_examples_and_hooks() {
+            // arrange
+            let body = |ctx: &mut Context<CloneCounter>| {
+                ctx.example_mut("mutates the environment", |_| {});
+                ctx.context("a context", |ctx| {
+                    ctx.before_each(|_| {});
+                    ctx.example("reads the environment", |_| {});
+                });
+            };
+            // act
+            let (cloned, reused) = (count_clones(false, body), count_clones(true, body));
+            // assert
+            assert_eq!(cloned, reused);
+        }
+
+        #[test]
+        fn it_hands_read_only_examples_of_serial_contexts_the_environment_itself() {
+            // arrange
+            let body = |ctx: &mut Context<CloneCounter>| {
+                ctx.serial_context("a serial context", |ctx| {
+                    for _ in 0..3 {
+                        ctx.example("reads the environment", |_| {});
+                    }
+                });
+            };
+            // act
+            let cloned = count_clones_in(true, false, body);
+            let reused = count_clones_in(true, true, body);
+            // assert
+            assert_eq!(cloned - 3, reused);
+        }
+
+        #[test]
+        fn it_clones_the_environment_of_mutable_examples_only_once() {
+            // arrange
+            let mutable = |ctx: &mut Context<CloneCounter>| {
+                ctx.example_mut("mutates the environment", |_| {});
+            };
+            let immutable = |ctx: &mut Context<CloneCounter>| {
+                ctx.example("reads the environment", |_| {});
+            };
+            // act
+            let (mutable, immutable) =
+                (count_clones(false, mutable), count_clones(false, immutable));
+            // assert
+            assert_eq!(immutable, mutable);
+        }
+    }
+
+    mod dry_run {
+        use super::*;
+
+        use block::suite;
+        use header::ExampleHeader;
+        use std::sync::atomic::*;
+
+        #[derive(Default)]
+        struct ExampleCounter {
+            examples: AtomicUsize,
+        }
+
+        impl RunnerObserver for ExampleCounter {
+            fn exit_example(
+                &self,
+                _runner: &Runner,
+                _header: &ExampleHeader,
+                _report: &ExampleReport,
+            ) {
+                self.examples.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn it_runs_neither_examples_nor_hooks() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .dry_run(true)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let observer = Arc::new(ExampleCounter::default());
+            let runner = Runner::new(config, vec![observer.clone()]);
+            let called = Arc::new(AtomicBool::new(false));
+            let (hook_called, example_called) = (called.clone(), called.clone());
+            let suite = suite("a suite", (), move |ctx| {
+                ctx.before_all(move |_| hook_called.store(true, Ordering::SeqCst));
+                ctx.context("a context", move |ctx| {
+                    ctx.example("panics", move |_| -> () {
+                        example_called.store(true, Ordering::SeqCst);
+                        panic!("should not have been called")
+                    });
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            assert!(!called.load(Ordering::SeqCst));
+            assert_eq!((0, 1), (report.get_passed(), report.get_ignored()));
+            assert_eq!(1, observer.examples.load(Ordering::SeqCst));
+        }
+    }
+
+    mod cancellation {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_ignores_blocks_once_cancelled() {
+            // arrange
+            let token = CancellationToken::new();
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .cancellation(token.clone())
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", token, |ctx| {
+                ctx.example("first", |token| token.cancel());
+                ctx.example("second", |_| {});
+                ctx.context("a context", |ctx| {
+                    ctx.example("third", |_| {});
+                });
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!((1, 2), (report.get_passed(), report.get_ignored()));
+            let example = report
+                .get_context()
+                .find_example(&["a context", "third"])
+                .unwrap();
+            let expected = ExampleResult::Ignored(Some("cancelled".into()));
+            assert_eq!(&expected, example.get_result());
+        }
+    }
+
+    mod max_failures {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_stops_after_the_given_number_of_failures() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .max_failures(2)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let evaluated = Arc::new(AtomicU32::new(0));
+            let suite = suite("a suite", (), |ctx| {
+                for i in 0..5 {
+                    let evaluated = evaluated.clone();
+                    ctx.example(format!("example {}", i), move |_| {
+                        evaluated.fetch_add(1, Ordering::SeqCst);
+                        i == 1
+                    });
+                }
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(3, evaluated.load(Ordering::SeqCst));
+            assert_eq!(
+                (1, 2, 2),
+                (
+                    report.get_passed(),
+                    report.get_failed(),
+                    report.get_ignored()
+                )
+            );
+            let example = report.get_context().find_example(&["example 3"]).unwrap();
+            let expected =
+                ExampleResult::Ignored(Some("maximum number of failures reached".into()));
+            assert_eq!(&expected, example.get_result());
+        }
+
+        #[test]
+        fn it_starts_counting_over_with_each_run() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .max_failures(1)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("fails", |_| false);
+                ctx.example("passes", |_| true);
+            });
+            // act
+            let _ = runner.run(&suite);
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(
+                (0, 1, 1),
+                (
+                    report.get_passed(),
+                    report.get_failed(),
+                    report.get_ignored()
+                )
+            );
+        }
+
+        #[test]
+        fn it_counts_failures_of_concurrent_runs_apart() {
+            use std::sync::Barrier;
+            use std::thread;
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .max_failures(1)
+                .build()
+                .unwrap();
+            let runner = Arc::new(Runner::new(config, vec![]));
+            let failed = Arc::new(Barrier::new(2));
+            let done = Arc::new(Barrier::new(2));
+            let failing = {
+                let (runner, failed, done) = (runner.clone(), failed.clone(), done.clone());
+                thread::spawn(move || {
+                    let suite = suite("a failing suite", (), move |ctx| {
+                        ctx.example("fails", move |_| {
+                            failed.wait();
+                            false
+                        });
+                    });
+                    let report = runner.run(&suite);
+                    done.wait();
+                    report
+                })
+            };
+            let suite = suite("a concurrent suite", (), move |ctx| {
+                ctx.example("waits for the other run", move |_| {
+                    failed.wait();
+                    done.wait();
+                    true
+                });
+                ctx.example("fails", |_| false);
+                ctx.example("is skipped", |_| true);
+            });
+            // act
+            let report = runner.run(&suite);
+            // assert
+            assert_eq!(1, failing.join().unwrap().get_failed());
+            assert_eq!(
+                (1, 1, 1),
+                (
+                    report.get_passed(),
+                    report.get_failed(),
+                    report.get_ignored()
+                )
+            );
+        }
+    }
+
+    mod dump_env_on_failure {
+        use super::*;
+
+        use block::suite;
+
+        #[derive(Clone, Debug)]
+        struct Environment {
+            name: &'static str,
+            values: Vec<u32>,
+        }
+
+        fn run(mut config: ConfigurationBuilder) -> SuiteReport {
+            let config = config
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let environment = Environment {
+                name: "an environment",
+                values: vec![1, 2],
+            };
+            let suite = suite("a suite", environment, |ctx| {
+                ctx.example("passes", |_| true);
+                ctx.example_mut("fails", |env| {
+                    env.values.push(3);
+                    ExampleResult::failure(Some("boom".to_owned()))
+                });
+            });
+            runner.run(&suite)
+        }
+
+        fn message(report: &SuiteReport, name: &str) -> Option<String> {
+            match *report
+                .get_context()
+                .find_example(&[name])
+                .unwrap()
+                .get_result()
+            {
+                ExampleResult::Failure { ref message, .. } => message.clone(),
+                _ => None,
+            }
+        }
+
+        #[test]
+        fn it_appends_the_environment_to_failure_messages() {
+            // arrange
+            let mut config = ConfigurationBuilder::default();
+            config.dump_env_on_failure(true);
+            // act
+            let report = run(config);
+            // assert
+            let expected = Environment {
+                name: "an environment",
+                values: vec![1, 2, 3],
+            };
+            let expected = format!("boom\nenvironment: {:#?}", expected);
+            assert_eq!(Some(expected), message(&report, "fails"));
+            assert_eq!(None, message(&report, "passes"));
+        }
+
+        #[test]
+        fn it_truncates_long_dumps() {
+            // arrange
+            let mut config = ConfigurationBuilder::default();
+            config.dump_env_on_failure(true).max_env_dump_len(5);
+            // act
+            let report = run(config);
+            // assert
+            let expected = "boom\nenvironment: Envir... (truncated)".to_owned();
+            assert_eq!(Some(expected), message(&report, "fails"));
+        }
+
+        #[test]
+        fn it_leaves_messages_alone_by_default() {
+            // arrange
+            let config = ConfigurationBuilder::default();
+            // act
+            let report = run(config);
+            // assert
+            assert_eq!(Some("boom".to_owned()), message(&report, "fails"));
+        }
+    }
+
+    mod captured_output {
+        use super::*;
+
+        use block::suite;
+
+        #[test]
+        fn it_keeps_the_panic_output_of_failing_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example("panics", |_| -> () { panic!("captured panic") });
+                ctx.example("passes", |_| {});
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            let context = report.get_context();
+            let output = context
+                .find_example(&["panics"])
+                .unwrap()
+                .get_captured_output();
+            assert!(output.unwrap().contains("captured panic"));
+            let output = context
+                .find_example(&["passes"])
+                .unwrap()
+                .get_captured_output();
+            assert_eq!(None, output);
+        }
+    }
+
+    mod rerun_failures {
+        use super::*;
+
+        use block::suite;
+        use std::sync::atomic::*;
+
+        #[test]
+        fn it_reruns_only_the_failed_examples() {
+            // arrange
+            let runner = Runner::new(
+                ConfigurationBuilder::default()
+                    .exit_on_failure(false)
+                    .build()
+                    .unwrap(),
+                vec![],
+            );
+            let call_counter = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", call_counter.clone(), |ctx| {
+                ctx.example("passes", |counter| {
+                    counter.fetch_add(1, Ordering::SeqCst) < 1000
+                });
+                ctx.context("a context", |ctx| {
+                    ctx.example("fails", |counter| {
+                        counter.fetch_add(1, Ordering::SeqCst) > 1000
+                    });
+                    ctx.example("passes", |counter| {
+                        counter.fetch_add(1, Ordering::SeqCst) < 1000
+                    });
+                });
+                ctx.scope(|ctx| {
+                    ctx.example("fails too", |counter| {
+                        counter.fetch_add(1, Ordering::SeqCst) > 1000
+                    });
+                });
+            });
+            let report = runner.run(&suite);
+            call_counter.store(0, Ordering::SeqCst);
+            // act
+            let rerun_report = runner.rerun_failures(&suite, &report);
+            // assert
+            assert_eq!(2, report.get_failed());
+            assert_eq!(2, call_counter.load(Ordering::SeqCst));
+            assert_eq!(report.get_failed(), rerun_report.get_failed());
+            assert_eq!(0, rerun_report.get_passed());
+            assert_eq!(report.failed_examples(), rerun_report.failed_examples());
+        }
+    }
+
+    mod run_one {
+        use super::*;
+
+        use block::suite;
+        use header::ExampleHeader;
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Mutex;
+
+        fn subject(
+            calls: Arc<Mutex<Vec<&'static str>>>,
+        ) -> Suite<'static, Arc<Mutex<Vec<&'static str>>>> {
+            suite("a suite", calls, |ctx| {
+                ctx.before_each(|calls| calls.lock().unwrap().push("before suite"));
+                ctx.example("first", |calls| calls.lock().unwrap().push("first"));
+                ctx.context("a context", |ctx| {
+                    ctx.before_each(|calls| calls.lock().unwrap().push("before context"));
+                    ctx.example("second", |calls| calls.lock().unwrap().push("second"));
+                    ctx.example("third", |calls| {
+                        calls.lock().unwrap().push("third");
+                        false
+                    });
+                });
+            })
+        }
+
+        #[test]
+        fn it_runs_only_the_example_at_the_path() {
+            // arrange
+            let runner = Runner::default();
+            let calls = Arc::new(Mutex::new(vec![]));
+            let suite = subject(calls.clone());
+            // act
+            let report = runner.run_one(&suite, &["a context", "second"]);
+            // assert
+            let report = report.expect("example to have been found");
+            assert!(report.is_success());
+            assert_eq!(
+                vec!["before suite", "before context", "second"],
+                *calls.lock().unwrap()
+            );
+        }
+
+        #[test]
+        fn it_returns_the_report_of_a_failing_example() {
+            // arrange
+            let runner = Runner::new(
+                ConfigurationBuilder::default()
+                    .exit_on_failure(false)
+                    .build()
+                    .unwrap(),
+                vec![],
+            );
+            let suite = subject(Arc::new(Mutex::new(vec![])));
+            // act
+            let report = runner.run_one(&suite, &["a context", "third"]);
+            // assert
+            assert!(report.expect("example to have been found").is_failure());
+        }
+
+        /// Observer counting the examples it got notified of.
+        #[derive(Default)]
+        struct ExampleCounter {
+            entered: AtomicUsize,
+            exited: AtomicUsize,
+        }
+
+        impl RunnerObserver for ExampleCounter {
+            fn enter_example(&self, _runner: &Runner, _header: &ExampleHeader) {
+                self.entered.fetch_add(1, Ordering::SeqCst);
+            }
+
+            fn exit_example(
+                &self,
+                _runner: &Runner,
+                _header: &ExampleHeader,
+                _report: &ExampleReport,
+            ) {
+                self.exited.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn it_neither_exits_nor_notifies_of_other_examples() {
+            // arrange
+            let counter = Arc::new(ExampleCounter::default());
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(true)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![counter.clone()]);
+            let suite = subject(Arc::new(Mutex::new(vec![])));
+            // act
+            let report = runner.run_one(&suite, &["a context", "third"]);
+            // assert
+            assert!(report.expect("example to have been found").is_failure());
+            assert!(!runner.should_exit.load(Ordering::SeqCst));
+            assert_eq!(1, counter.entered.load(Ordering::SeqCst));
+            assert_eq!(1, counter.exited.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_returns_none_for_unknown_paths() {
+            // arrange
+            let runner = Runner::default();
+            let calls = Arc::new(Mutex::new(vec![]));
+            let suite = subject(calls.clone());
+            // act
+            let report = runner.run_one(&suite, &["second"]);
+            // assert
+            assert!(report.is_none());
+            assert!(calls.lock().unwrap().is_empty());
+        }
+    }
+
+    mod impl_drop_for_runner {
+        use super::*;
+
+        #[test]
+        #[should_panic]
+        fn it_should_abort() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(true)
+                .build()
+                .unwrap();
+            // act
+            {
+                let runner = Runner::new(config, vec![]);
+                runner.should_exit.store(true, Ordering::SeqCst);
+            }
+            // assert
+            // test should panic
+        }
+
+        #[test]
+        #[should_panic(expected = "exit code 42")]
+        fn it_should_abort_with_the_configured_exit_code() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .exit_on_failure(true)
+                .failure_exit_code(42)
+                .build()
+                .unwrap();
+            // act
+            {
+                let runner = Runner::new(config, vec![]);
+                runner.should_exit.store(true, Ordering::SeqCst);
+            }
+            // assert
+            // test should panic
+        }
+    }
+
+    mod impl_visitor_example_for_runner {
+        use super::*;
+
+        use block::suite;
+        use header::*;
+        use report::*;
+        use std::sync::atomic::*;
+
+        #[derive(Default, Debug, Clone)]
+        struct SpyObserver {
+            enter_example: Arc<AtomicBool>,
+            exit_example: Arc<AtomicBool>,
+        }
+        impl RunnerObserver for SpyObserver {
+            fn enter_example(&self, _runner: &Runner, _header: &ExampleHeader) {
+                self.enter_example.store(true, Ordering::SeqCst)
+            }
+
+            fn exit_example(
+                &self,
+                _runner: &Runner,
+                _header: &ExampleHeader,
+                _report: &ExampleReport,
+            ) {
+                self.exit_example.store(true, Ordering::SeqCst)
+            }
+        }
+
+        #[test]
+        fn it_can_be_called() {
+            // arrange
+            let runner = Runner::default();
+            let example = Example::fixture_success();
+            // act
+            // assert
+            let _ = runner.visit(&example, &mut ());
+        }
+
+        #[test]
+        fn it_calls_observer_hooks() {
+            // arrange
+            let spy = Arc::new(SpyObserver::default());
+            let runner = Runner::new(Configuration::default(), vec![spy.clone()]);
+            let example = Example::fixture_success();
+            // act
+            let _ = runner.visit(&example, &mut ());
+            // assert
+            assert!(spy.enter_example.load(Ordering::SeqCst));
+            assert!(spy.exit_example.load(Ordering::SeqCst))
+        }
+
+        #[test]
+        fn it_gives_an_isolated_mutable_env_to_mutable_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .parallel(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let suite = suite("a suite", vec![], |ctx| {
+                ctx.example_mut("mutates its env", |env: &mut Vec<usize>| {
+                    env.push(42);
+                    env.len() == 1
+                });
+                ctx.example("does not observe the mutation", |env| env.is_empty());
+            });
+            // act
+            let result = runner.try_run(&suite);
+            // assert
+            assert!(result.is_ok());
+        }
+
+        #[cfg(feature = "futures")]
+        #[test]
+        fn it_awaits_async_examples() {
+            use std::future::Future;
+            use std::pin::Pin;
+            use std::task::{Context, Poll};
+
+            /// Future being pending on its first poll, then resolving to `value`.
+            struct YieldOnce {
+                value: bool,
+                yielded: bool,
+            }
+
+            impl Future for YieldOnce {
+                type Output = bool;
+
+                fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<bool> {
+                    if self.yielded {
+                        Poll::Ready(self.value)
+                    } else {
+                        self.yielded = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                }
+            }
+
+            // arrange
+            let runner = Runner::new(
+                ConfigurationBuilder::default()
+                    .exit_on_failure(false)
+                    .build()
+                    .unwrap(),
+                vec![],
+            );
+            let suite = suite("a suite", (), |ctx| {
+                ctx.async_example("passes", |_| YieldOnce {
+                    value: true,
+                    yielded: false,
+                });
+                ctx.async_example("fails", |_| YieldOnce {
+                    value: false,
+                    yielded: false,
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            assert_eq!(1, report.get_passed());
+            assert_eq!(1, report.get_failed());
+        }
+
+        #[test]
+        fn it_retries_failing_examples() {
+            // arrange
+            let config = ConfigurationBuilder::default().retries(3).build().unwrap();
+            let runner = Runner::new(config, vec![]);
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", attempts.clone(), |ctx| {
+                ctx.example("fails twice, then passes", |attempts| {
+                    attempts.fetch_add(1, Ordering::SeqCst) >= 2
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            let example = match report.get_context().get_blocks()[0] {
+                BlockReport::Example(_, ref report) => report.clone(),
+                ref block => panic!("expected an example report, got {:?}", block),
+            };
+            assert!(example.is_success());
+            assert_eq!(3, example.get_attempts());
+            assert_eq!(2, example.get_failed_attempts().len());
+            assert_eq!(3, attempts.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_retries_mutable_examples_with_the_original_environment() {
+            // arrange
+            let config = ConfigurationBuilder::default().retries(1).build().unwrap();
+            let runner = Runner::new(config, vec![]);
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", 0, move |ctx| {
+                ctx.example_mut(
+                    "fails once, then sees the original environment",
+                    move |env| {
+                        *env += 1;
+                        attempts.fetch_add(1, Ordering::SeqCst) > 0 && *env == 1
+                    },
+                );
+            });
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            assert_eq!(1, report.unwrap().get_passed());
+        }
+
+        #[test]
+        fn it_reports_the_last_failure_once_out_of_retries() {
+            // arrange
+            let config = ConfigurationBuilder::default()
+                .retries(1)
+                .exit_on_failure(false)
+                .build()
+                .unwrap();
+            let runner = Runner::new(config, vec![]);
+            let attempts = Arc::new(AtomicUsize::new(0));
+            let suite = suite("a suite", attempts.clone(), |ctx| {
+                ctx.example("always fails", |attempts| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    false
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap_err();
+            // assert
+            assert_eq!(1, report.get_failed());
+            assert_eq!(2, attempts.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_gives_a_derived_env_to_examples_of_mapped_contexts() {
+            // arrange
+            #[derive(Clone, Debug)]
+            struct AppState {
+                name: &'static str,
+                users: Vec<&'static str>,
+            }
+            let runner = Runner::default();
+            let state = AppState {
+                name: "app",
+                users: vec!["alice", "bob"],
+            };
+            let suite = suite("a suite", state, |ctx| {
+                ctx.before_each(|state| state.users.push("carol"));
+                ctx.context_map(
+                    "the users",
+                    |state: &AppState| state.users.clone(),
+                    |ctx| {
+                        ctx.before_each(|users| users.push("dave"));
+                        ctx.it("got derived from the state", |users| {
+                            expect_eq(vec!["alice", "bob", "carol", "dave"], users.clone())
+                        });
+                    },
+                );
+                ctx.it("keeps the state intact", |state| state.name == "app");
+            });
+            // act
+            let report = runner.try_run(&suite);
+            // assert
+            let report = report.unwrap();
+            assert_eq!(2, report.get_passed());
+            assert_eq!(1, suite.num_contexts());
+        }
+
+        #[test]
+        fn it_measures_durations_with_the_runners_clock() {
+            use std::collections::VecDeque;
+            use std::time::{Duration, Instant};
+
+            /// Clock returning the scripted offsets from a fixed instant.
+            struct MockClock {
+                base: Instant,
+                offsets: Mutex<VecDeque<Duration>>,
+            }
+
+            impl Clock for MockClock {
+                fn now(&self) -> Instant {
+                    let offset = self.offsets.lock().unwrap().pop_front();
+                    self.base + offset.expect("no more scripted instants")
+                }
+            }
+
+            // arrange
+            let clock = MockClock {
+                base: Instant::now(),
+                offsets: Mutex::new(VecDeque::from(vec![
+                    Duration::from_millis(10),
+                    Duration::from_millis(52),
+                ])),
+            };
+            let runner = Runner::default().with_clock(Arc::new(clock));
+            let example = Example::fixture_success();
+            // act
+            let report = runner.visit(&example, &mut ());
+            // assert
+            assert_eq!(Duration::from_millis(42), report.get_duration());
+        }
+
+        #[test]
+        fn it_preserves_the_reason_of_ignored_examples() {
+            // arrange
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let suite = suite("suite", (), |ctx| {
+                ctx.context("context", |ctx| {
+                    ctx.xit("example", "not yet", |_| {});
+                });
+            });
+            // act
+            let report = runner.visit(&suite, &mut ());
+            // assert
+            let example = report
+                .get_context()
+                .find_example(&["context", "example"])
+                .unwrap();
+            let expected = ExampleResult::Ignored(Some("not yet".to_owned()));
+            assert_eq!(&expected, example.get_result());
+            assert_eq!(1, report.get_ignored());
+        }
+
+        #[test]
+        fn it_runs_no_hooks_for_ignored_examples() {
+            // arrange
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let calls = Arc::new(Mutex::new(vec![]));
+            let (before_calls, after_calls) = (calls.clone(), calls.clone());
+            let around_calls = calls.clone();
+            let suite = suite("suite", (), move |ctx| {
+                ctx.before_each(move |_| before_calls.lock().unwrap().push("before"));
+                ctx.after_each(move |_| after_calls.lock().unwrap().push("after"));
+                ctx.around(move |env, run| {
+                    around_calls.lock().unwrap().push("around");
+                    run.call(env);
+                });
+                ctx.xit("example", "not yet", |_| {});
+            });
+            // act
+            let report = runner.visit(&suite, &mut ());
+            // assert
+            assert_eq!(1, report.get_ignored());
+            assert!(calls.lock().unwrap().is_empty());
+        }
+
+        #[test]
+        fn it_ignores_skipped_contexts_with_the_reason() {
+            // arrange
+            let runner = Runner::new(Configuration::default(), vec![]);
+            let hook_runs = Arc::new(AtomicUsize::new(0));
+            let counter = hook_runs.clone();
+            let suite = suite("suite", (), move |ctx| {
+                ctx.before_each(move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+                ctx.skip_if(
+                    || true,
+                    "not today",
+                    |ctx| {
+                        ctx.it("first", |_| false);
+                        ctx.context("context", |ctx| {
+                            ctx.it("second", |_| false);
+                        });
+                    },
+                );
+                ctx.skip_if(
+                    || false,
+                    "not skipped",
+                    |ctx| {
+                        ctx.it("third", |_| {});
+                    },
+                );
+            });
+            // act
+            let report = runner.visit(&suite, &mut ());
+            // assert
+            let ignored = ExampleResult::Ignored(Some("not today".to_owned()));
+            let context = report.get_context();
+            assert_eq!(
+                &ignored,
+                context.find_example(&["first"]).unwrap().get_result()
+            );
+            assert_eq!(
+                &ignored,
+                context
+                    .find_example(&["context", "second"])
+                    .unwrap()
+                    .get_result()
+            );
+            assert_eq!(
+                (1, 0, 2),
+                (
+                    report.get_passed(),
+                    report.get_failed(),
+                    report.get_ignored()
+                )
+            );
+            // Only the example which wasn't skipped got its `before_each` run:
+            assert_eq!(1, hook_runs.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn it_passes_examples_panicking_as_expected() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example_should_panic("panics", None, |_| panic!("boom"));
+                ctx.example_should_panic("panics with message", Some("boom"), |_| {
+                    panic!("it went boom")
+                });
+            });
+            // act
+            let report = runner.try_run(&suite).unwrap();
+            // assert
+            assert_eq!(2, report.get_passed());
+        }
+
+        #[test]
+        fn it_fails_examples_not_panicking_as_expected() {
+            // arrange
+            let runner = Runner::default();
+            let suite = suite("a suite", (), |ctx| {
+                ctx.example_should_panic("does not panic", None, |_| {});
+                ctx.example_should_panic("panics differently", Some("boom"), |_| {
+                    panic!("it went bang")
+                });
+            });
             // act
-            runner.visit(&example, &mut ());
+            let report = runner.try_run(&suite).unwrap_err();
             // assert
-            assert_eq!(true, spy.enter_example.load(Ordering::SeqCst));
-            assert_eq!(true, spy.exit_example.load(Ordering::SeqCst))
+            assert_eq!(2, report.get_failed());
         }
 
         #[test]
@@ -702,9 +3652,9 @@ mod tests {
                 env.store(true, Ordering::SeqCst);
                 ExampleResult::Success
             });
-            runner.visit(&example, &mut environment);
+            let _ = runner.visit(&example, &mut environment);
             // assert
-            assert_eq!(true, environment.load(Ordering::SeqCst));
+            assert!(environment.load(Ordering::SeqCst));
         }
     }
 
@@ -718,7 +3668,7 @@ mod tests {
             let block = Block::Example(Example::fixture_success());
             // act
             // assert
-            runner.visit(&block, &mut ());
+            let _ = runner.visit(&block, &mut ());
         }
     }
 }
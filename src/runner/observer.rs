@@ -1,9 +1,22 @@
 //! Events are sent by the Runner to signal the progression in the test suite, with the results
 
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
 use header::{ContextHeader, ExampleHeader, SuiteHeader};
-use report::{ContextReport, ExampleReport, SuiteReport};
+use report::{BlockReport, ContextReport, ExampleReport, SuiteReport};
 use runner::Runner;
 
+/// The kind of a context's hook (i.e. `before_all`, `after_each`, …).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookKind {
+    BeforeAll,
+    BeforeEach,
+    AfterAll,
+    AfterEach,
+}
+
 /// `RunnerObserver`s can be attached to a [`Runner`](../runner/struct.Runner.html) to observe a
 #[allow(unused_variables)]
 pub trait RunnerObserver: Send + Sync {
@@ -13,6 +26,150 @@ pub trait RunnerObserver: Send + Sync {
     fn exit_context(&self, runner: &Runner, header: &ContextHeader, report: &ContextReport) {}
     fn enter_example(&self, runner: &Runner, header: &ExampleHeader) {}
     fn exit_example(&self, runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {}
+    /// Called as soon as one of the suite's top-level blocks has been evaluated,
+    /// with its (declaration) index within the suite.
+    fn exit_suite_block(&self, runner: &Runner, index: usize, report: &BlockReport) {}
+    fn enter_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {}
+    fn exit_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {}
+
+    /// Takes the first I/O error encountered by the observer (if any) since last being asked.
+    fn take_io_error(&self) -> Option<io::Error> {
+        None
+    }
+
+    /// Called once the suite has been evaluated (before notifying observers of exiting it),
+    /// returning `false` for considering the run failed (e.g. for having the process exit
+    /// on failure) even if all of its examples passed.
+    ///
+    /// The report of a vetoed run carries an error saying so, see `SuiteReport::get_error`.
+    fn finalize(&self, report: &SuiteReport) -> bool {
+        true
+    }
+
+    /// Called upon [beginning a session](struct.Runner.html#method.begin_session).
+    fn enter_session(&self, runner: &Runner) {}
+    /// Called upon [ending a session](struct.Runner.html#method.end_session),
+    /// with the combined report of the suites run during it.
+    fn exit_session(&self, runner: &Runner, report: &SuiteReport) {}
+}
+
+/// Like [`RunnerObserver`](trait.RunnerObserver.html), but without requiring `Send + Sync`,
+/// for observers of serial runs only (e.g. ones keeping their state in a `RefCell`),
+/// see [`Runner::run_serial`](struct.Runner.html#method.run_serial).
+#[allow(unused_variables)]
+pub trait SerialRunnerObserver {
+    fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {}
+    fn exit_suite(&self, runner: &Runner, header: &SuiteHeader, report: &SuiteReport) {}
+    fn enter_context(&self, runner: &Runner, header: &ContextHeader) {}
+    fn exit_context(&self, runner: &Runner, header: &ContextHeader, report: &ContextReport) {}
+    fn enter_example(&self, runner: &Runner, header: &ExampleHeader) {}
+    fn exit_example(&self, runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {}
+    fn exit_suite_block(&self, runner: &Runner, index: usize, report: &BlockReport) {}
+    fn enter_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {}
+    fn exit_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {}
+
+    /// Takes the first I/O error encountered by the observer (if any) since last being asked.
+    fn take_io_error(&self) -> Option<io::Error> {
+        None
+    }
+
+    /// Called once the suite has been evaluated (before notifying observers of exiting it),
+    /// returning `false` for considering the run failed (e.g. for having the process exit
+    /// on failure) even if all of its examples passed.
+    ///
+    /// The report of a vetoed run carries an error saying so, see `SuiteReport::get_error`.
+    fn finalize(&self, report: &SuiteReport) -> bool {
+        true
+    }
+
+    fn enter_session(&self, runner: &Runner) {}
+    fn exit_session(&self, runner: &Runner, report: &SuiteReport) {}
+}
+
+/// Has a `RunnerObserver` observe alongside `SerialRunnerObserver`s.
+pub(crate) struct Forward<'a>(pub(crate) &'a dyn RunnerObserver);
+
+impl<'a> SerialRunnerObserver for Forward<'a> {
+    fn enter_suite(&self, runner: &Runner, header: &SuiteHeader) {
+        self.0.enter_suite(runner, header)
+    }
+    fn exit_suite(&self, runner: &Runner, header: &SuiteHeader, report: &SuiteReport) {
+        self.0.exit_suite(runner, header, report)
+    }
+    fn enter_context(&self, runner: &Runner, header: &ContextHeader) {
+        self.0.enter_context(runner, header)
+    }
+    fn exit_context(&self, runner: &Runner, header: &ContextHeader, report: &ContextReport) {
+        self.0.exit_context(runner, header, report)
+    }
+    fn enter_example(&self, runner: &Runner, header: &ExampleHeader) {
+        self.0.enter_example(runner, header)
+    }
+    fn exit_example(&self, runner: &Runner, header: &ExampleHeader, report: &ExampleReport) {
+        self.0.exit_example(runner, header, report)
+    }
+    fn exit_suite_block(&self, runner: &Runner, index: usize, report: &BlockReport) {
+        self.0.exit_suite_block(runner, index, report)
+    }
+    fn enter_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {
+        self.0.enter_hook(runner, kind, header)
+    }
+    fn exit_hook(&self, runner: &Runner, kind: HookKind, header: Option<&ContextHeader>) {
+        self.0.exit_hook(runner, kind, header)
+    }
+    fn take_io_error(&self) -> Option<io::Error> {
+        self.0.take_io_error()
+    }
+    fn finalize(&self, report: &SuiteReport) -> bool {
+        self.0.finalize(report)
+    }
+    fn enter_session(&self, runner: &Runner) {
+        self.0.enter_session(runner)
+    }
+    fn exit_session(&self, runner: &Runner, report: &SuiteReport) {
+        self.0.exit_session(runner, report)
+    }
+}
+
+thread_local! {
+    /// The serial observers of the serial runs on the current thread, by the address of their runner.
+    static SERIAL_OBSERVERS: RefCell<Vec<(usize, Rc<dyn SerialRunnerObserver>)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Attaches the serial observers to the runner (identified by `runner_id`) on the current thread
+/// while calling `body`.
+pub(crate) fn with_serial_observers<F, R>(
+    runner_id: usize,
+    observers: Vec<Rc<dyn SerialRunnerObserver>>,
+    body: F,
+) -> R
+where
+    F: FnOnce() -> R,
+{
+    SERIAL_OBSERVERS.with(|attached| {
+        let observers = observers.into_iter().map(|observer| (runner_id, observer));
+        attached.borrow_mut().extend(observers)
+    });
+    let result = body();
+    SERIAL_OBSERVERS.with(|attached| {
+        attached
+            .borrow_mut()
+            .retain(|&(attached_id, _)| attached_id != runner_id)
+    });
+    result
+}
+
+/// The serial observers attached to the runner (identified by `runner_id`) on the current thread.
+pub(crate) fn serial_observers(runner_id: usize) -> Vec<Rc<dyn SerialRunnerObserver>> {
+    SERIAL_OBSERVERS.with(|attached| {
+        attached
+            .borrow()
+            .iter()
+            .filter(|&&(attached_id, _)| attached_id == runner_id)
+            .map(|(_, observer)| observer.clone())
+            .collect()
+    })
 }
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+//! Reporters get handed a suite's final report once it has been evaluated.
+
+use report::SuiteReport;
+
+/// `Reporter`s can be attached to a [`Runner`](../runner/struct.Runner.html) to process
+/// a suite's final report (e.g. by writing it to a file in some machine-readable format).
+///
+/// Unlike a [`RunnerObserver`](trait.RunnerObserver.html) a reporter doesn't get notified
+/// of the run's progression, but only gets called exactly once per run, after it has finished.
+pub trait Reporter: Send + Sync {
+    fn report(&self, report: &SuiteReport);
+}
+
+/// A callback to be called with the final report of each run,
+/// see [`Runner::on_finish`](../runner/struct.Runner.html#method.on_finish).
+pub type FinishCallback = Box<dyn Fn(&SuiteReport) + Send + Sync>;
+
+/// Reporter calling a callback.
+pub(crate) struct CallbackReporter(pub(crate) FinishCallback);
+
+impl Reporter for CallbackReporter {
+    fn report(&self, report: &SuiteReport) {
+        (self.0)(report)
+    }
+}
@@ -0,0 +1,76 @@
+//! Seeded shuffling of a context's blocks, see
+//! [`Configuration.shuffle`](struct.Configuration.html#structfield.shuffle).
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Generates a fresh seed, differing from run to run.
+pub(crate) fn random_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
+
+/// A random permutation of `0..len`, depending only on `seed` and the context's `path`.
+///
+/// Deriving the permutation from the path (rather than from a shared generator) keeps it
+/// stable regardless of the order in which contexts get evaluated (e.g. in parallel).
+pub(crate) fn shuffled_indices<S>(len: usize, seed: u64, path: &[S]) -> Vec<usize>
+where
+    S: AsRef<str>,
+{
+    let mut state = seed;
+    for name in path {
+        for byte in name.as_ref().bytes().chain(Some(0)) {
+            state = (state ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    let mut indices: Vec<usize> = (0..len).collect();
+    // Fisher-Yates:
+    for i in (1..len).rev() {
+        let j = (split_mix(&mut state) % (i as u64 + 1)) as usize;
+        indices.swap(i, j);
+    }
+    indices
+}
+
+/// The SplitMix64 generator, see http://xoshiro.di.unimi.it/splitmix64.c
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_a_permutation() {
+        // arrange
+        let len = 100;
+        // act
+        let mut indices = shuffled_indices(len, 42, &["a context"]);
+        // assert
+        assert_ne!((0..len).collect::<Vec<_>>(), indices);
+        indices.sort();
+        assert_eq!((0..len).collect::<Vec<_>>(), indices);
+    }
+
+    #[test]
+    fn it_is_deterministic() {
+        assert_eq!(
+            shuffled_indices(10, 42, &["a context"]),
+            shuffled_indices(10, 42, &["a context"])
+        );
+        assert_ne!(
+            shuffled_indices(10, 42, &["a context"]),
+            shuffled_indices(10, 43, &["a context"])
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Forcing colors on means overriding `colored`'s process-wide choice,
+//! which would leak into the unit tests comparing uncolored output,
+//! hence these tests getting a test binary (and thus process) of their own.
+
+#![cfg(feature = "color")]
+
+extern crate colored;
+extern crate rspec;
+
+use rspec::header::{ExampleHeader, ExampleLabel, SuiteHeader, SuiteLabel};
+use rspec::runner::RunnerObserver;
+use rspec::{ConfigurationBuilder, Logger, Runner};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// A writer whose output remains accessible once it has been handed to a logger.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn it_styles_the_suite_header_line_only() {
+    // arrange
+    colored::control::set_override(true);
+    let buffer = SharedBuffer::default();
+    let logger = Logger::new(buffer.clone());
+    let configuration = ConfigurationBuilder::default()
+        .parallel(false)
+        .build()
+        .unwrap();
+    let runner = Runner::new(configuration, vec![]);
+    let suite = SuiteHeader::new(SuiteLabel::Suite, "a suite");
+    let example = ExampleHeader::new(ExampleLabel::It, "an example");
+    // act
+    logger.enter_suite(&runner, &suite);
+    logger.enter_example(&runner, &example);
+    // assert
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let suite_line = output.lines().find(|line| line.contains("a suite"));
+    let example_line = output.lines().find(|line| line.contains("an example"));
+    assert!(
+        suite_line.is_some_and(|line| line.contains('\u{1b}')),
+        "{:?}",
+        output
+    );
+    assert!(
+        example_line.is_some_and(|line| !line.contains('\u{1b}')),
+        "{:?}",
+        output
+    );
+}